@@ -0,0 +1,220 @@
+//! Arithmetic on durations in nanoseconds.
+use core::convert::TryFrom;
+
+use crate::{parse_duration, Error};
+
+/// Rounding selects which way a duration is rounded to a multiple of
+/// another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward positive infinity.
+    Ceil,
+    /// Round to the nearest multiple, halfway values away from zero.
+    Nearest,
+}
+
+/// div_rem divides a duration by a unit or another duration, returning the
+/// quotient and the remainder in nanoseconds.
+///
+/// The quotient is truncated toward zero, so the remainder has the sign of
+/// ns and `quotient * by + remainder == ns` always holds.
+///
+/// ```rust
+/// use go_parse_duration::{div_rem, Unit};
+///
+/// assert_eq!(div_rem(150000000000, Unit::Minute), (2, 30000000000));
+/// assert_eq!(div_rem(-150000000000, Unit::Minute), (-2, -30000000000));
+/// assert_eq!(div_rem(150000000000, 45000000000i64), (3, 15000000000));
+/// ```
+///
+/// # Panics
+///
+/// Panics if by is zero, or if the quotient overflows (`i64::MIN` divided by
+/// -1).
+pub fn div_rem<D: Into<i64>>(ns: i64, by: D) -> (i64, i64) {
+    let by = by.into();
+    (ns / by, ns % by)
+}
+
+/// align_to rounds a duration to a multiple of step, e.g. to align "1h3m"
+/// to 15-minute boundaries.
+///
+/// It returns None if step is not positive or the result overflows.
+///
+/// ```rust
+/// use go_parse_duration::{align_to, parse_duration, Rounding};
+///
+/// let d = parse_duration("1h3m").unwrap();
+/// let step = parse_duration("15m").unwrap();
+/// assert_eq!(align_to(d, step, Rounding::Floor), Some(parse_duration("1h").unwrap()));
+/// assert_eq!(align_to(d, step, Rounding::Ceil), Some(parse_duration("1h15m").unwrap()));
+/// assert_eq!(align_to(d, step, Rounding::Nearest), Some(parse_duration("1h").unwrap()));
+/// ```
+pub fn align_to<D: Into<i64>>(ns: i64, step: D, mode: Rounding) -> Option<i64> {
+    let step = step.into();
+    if step <= 0 {
+        return None;
+    }
+    // Work in i128 so rounding up near the bounds can't overflow.
+    let (ns, step) = (i128::from(ns), i128::from(step));
+    let floor = ns.div_euclid(step) * step;
+    let rem = ns - floor;
+    let aligned = match mode {
+        Rounding::Floor => floor,
+        Rounding::Ceil if rem == 0 => floor,
+        Rounding::Ceil => floor + step,
+        Rounding::Nearest if rem * 2 < step => floor,
+        Rounding::Nearest if rem * 2 > step => floor + step,
+        Rounding::Nearest if ns < 0 => floor,
+        Rounding::Nearest => floor + step,
+    };
+    i64::try_from(aligned).ok()
+}
+
+/// truncate returns the duration rounded toward zero to a multiple of m, as
+/// Go's `Duration.Truncate` does. If m is not positive, the duration is
+/// returned unchanged.
+///
+/// ```rust
+/// use go_parse_duration::{truncate, Unit};
+///
+/// assert_eq!(truncate(5430000000000, Unit::Hour), 3600000000000);
+/// assert_eq!(truncate(-5430000000000, Unit::Hour), -3600000000000);
+/// ```
+pub fn truncate<D: Into<i64>>(ns: i64, m: D) -> i64 {
+    let m = m.into();
+    if m <= 0 {
+        return ns;
+    }
+    ns - ns % m
+}
+
+/// round returns the duration rounded to the nearest multiple of m, as Go's
+/// `Duration.Round` does. Halfway values are rounded away from zero, and if
+/// the result overflows it's `i64::MAX` or `i64::MIN`. If m is not positive,
+/// the duration is returned unchanged.
+///
+/// ```rust
+/// use go_parse_duration::{round, Unit};
+///
+/// assert_eq!(round(5430000000000, Unit::Hour), 7200000000000);
+/// assert_eq!(round(-1500000, Unit::Millisecond), -2000000);
+/// ```
+pub fn round<D: Into<i64>>(ns: i64, m: D) -> i64 {
+    let m = m.into();
+    if m <= 0 {
+        return ns;
+    }
+    // less_than_half reports whether x+x < y, without overflowing.
+    let less_than_half = |x: i64, y: i64| (x as u64) + (x as u64) < y as u64;
+    let mut r = ns % m;
+    if ns < 0 {
+        r = -r;
+        if less_than_half(r, m) {
+            return ns + r;
+        }
+        return ns.checked_sub(m - r).unwrap_or(i64::MIN);
+    }
+    if less_than_half(r, m) {
+        return ns - r;
+    }
+    ns.checked_add(m - r).unwrap_or(i64::MAX)
+}
+
+/// ratio returns a divided by b as a float, e.g. 0.75 for 45 minutes over an
+/// hour.
+///
+/// As with any float division, a zero b gives an infinite or NaN result.
+pub fn ratio(a: i64, b: i64) -> f64 {
+    a as f64 / b as f64
+}
+
+/// percent_of parses two duration strings and returns what percentage of
+/// the second the first one is.
+///
+/// ```rust
+/// use go_parse_duration::percent_of;
+///
+/// assert_eq!(percent_of("45m", "1h"), Ok(75.0));
+/// ```
+pub fn percent_of(part: &str, whole: &str) -> Result<f64, Error> {
+    Ok(ratio(parse_duration(part)?, parse_duration(whole)?) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GoDuration, Unit};
+
+    #[test]
+    fn test_div_rem() {
+        assert_eq!(div_rem(5400000000000, Unit::Hour), (1, 1800000000000));
+        assert_eq!(div_rem(-5400000000000, Unit::Hour), (-1, -1800000000000));
+        assert_eq!(div_rem(999, Unit::Microsecond), (0, 999));
+        assert_eq!(div_rem(7, GoDuration::from_nanos(-2)), (-3, 1));
+        let (q, r) = div_rem(i64::MIN, Unit::Second);
+        assert_eq!(q * Unit::Second.nanos() + r, i64::MIN);
+    }
+
+    #[test]
+    fn test_align_to() {
+        use Rounding::*;
+
+        let m = Unit::Minute.nanos();
+        assert_eq!(align_to(63 * m, 15 * m, Floor), Some(60 * m));
+        assert_eq!(align_to(63 * m, 15 * m, Ceil), Some(75 * m));
+        assert_eq!(align_to(60 * m, 15 * m, Ceil), Some(60 * m));
+        assert_eq!(align_to(68 * m, 15 * m, Nearest), Some(75 * m));
+        assert_eq!(align_to(-63 * m, 15 * m, Floor), Some(-75 * m));
+        assert_eq!(align_to(-63 * m, 15 * m, Ceil), Some(-60 * m));
+        assert_eq!(align_to(15, 10, Nearest), Some(20));
+        assert_eq!(align_to(-15, 10, Nearest), Some(-20));
+        assert_eq!(align_to(5, 0, Floor), None);
+        assert_eq!(align_to(i64::MAX, Unit::Hour, Ceil), None);
+        assert_eq!(align_to(i64::MIN, Unit::Hour, Floor), None);
+    }
+
+    #[test]
+    fn test_round_truncate() {
+        // Cases from Go's truncateRoundTests and TestDurationRound.
+        let tests: [(i64, i64, i64, i64); 10] = [
+            // (d, m, truncated, rounded)
+            (-4, -1, -4, -4),
+            (-4, 0, -4, -4),
+            (-4, 3, -3, -3),
+            (-5, 3, -3, -6),
+            (5, 2, 4, 6),
+            (-5, 2, -4, -6),
+            (6, 4, 4, 8),
+            (7, 4, 4, 8),
+            (1, i64::MAX, 0, 0),
+            (i64::MAX - 10, 3, i64::MAX - 10, i64::MAX - 10),
+        ];
+        for &(d, m, truncated, rounded) in tests.iter() {
+            assert_eq!(truncate(d, m), truncated, "truncate({}, {})", d, m);
+            assert_eq!(round(d, m), rounded, "round({}, {})", d, m);
+        }
+        let h = Unit::Hour.nanos();
+        assert_eq!(round(i64::MAX, h), i64::MAX);
+        assert_eq!(round(i64::MIN, h), i64::MIN);
+        assert_eq!(round(i64::MAX, 2), i64::MAX);
+        assert_eq!(round(i64::MIN, 2), i64::MIN);
+        assert_eq!(truncate(i64::MIN, 2), i64::MIN);
+        assert_eq!(round(i64::MIN, i64::MAX), -i64::MAX);
+        assert_eq!(round(5400000000000, GoDuration::from_nanos(h)), 2 * h);
+    }
+
+    #[test]
+    fn test_ratio() -> Result<(), Error> {
+        let h = Unit::Hour.nanos();
+        assert_eq!(ratio(3 * h, 4 * h), 0.75);
+        assert_eq!(ratio(-h, 2 * h), -0.5);
+        assert!(ratio(h, 0).is_infinite());
+        assert_eq!(percent_of("90s", "1m")?, 150.0);
+        assert_eq!(percent_of("0s", "1m")?, 0.0);
+        assert!(percent_of("1x", "1m").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+// Conversions between durations and chrono's, with the chrono feature.
+use std::convert::TryFrom;
+
+use crate::{parse_duration, Error, GoDuration, OverflowError};
+
+/// parse_chrono parses a duration string and returns it as a
+/// `chrono::Duration`.
+///
+/// ```rust
+/// use go_parse_duration::parse_chrono;
+///
+/// assert_eq!(parse_chrono("1m"), Ok(chrono::Duration::minutes(1)));
+/// ```
+pub fn parse_chrono(s: &str) -> Result<chrono::Duration, Error> {
+    parse_duration(s).map(chrono::Duration::nanoseconds)
+}
+
+impl From<GoDuration> for chrono::Duration {
+    fn from(d: GoDuration) -> chrono::Duration {
+        chrono::Duration::nanoseconds(d.as_nanos())
+    }
+}
+
+/// chrono durations longer than about 292 years don't fit in nanoseconds.
+impl TryFrom<chrono::Duration> for GoDuration {
+    type Error = OverflowError;
+
+    fn try_from(d: chrono::Duration) -> Result<GoDuration, OverflowError> {
+        d.num_nanoseconds()
+            .map(GoDuration::from_nanos)
+            .ok_or(OverflowError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chrono() -> Result<(), Error> {
+        assert_eq!(parse_chrono("1h30m")?, chrono::Duration::minutes(90));
+        assert_eq!(
+            parse_chrono("-1.5us")?,
+            chrono::Duration::nanoseconds(-1500)
+        );
+        assert!(parse_chrono("1d").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversions() {
+        let d = GoDuration::from_nanos(i64::MIN);
+        assert_eq!(GoDuration::try_from(chrono::Duration::from(d)), Ok(d));
+        assert_eq!(
+            GoDuration::try_from(chrono::Duration::days(365 * 300)),
+            Err(OverflowError)
+        );
+    }
+}
@@ -0,0 +1,83 @@
+// Parsing command-line arguments as durations with clap, with the clap
+// feature.
+use std::ffi::OsStr;
+
+use clap::builder::{StringValueParser, TypedValueParser, ValueParserFactory};
+
+use crate::GoDuration;
+
+/// DurationValueParser is a clap value parser for Go duration strings. Parse
+/// errors are reported by clap like those of its own parsers.
+///
+/// It's the parser `clap::value_parser!(GoDuration)` returns, so a
+/// `GoDuration` field of a derived `Parser` needs no attribute:
+///
+/// ```rust
+/// use clap::{Arg, Command};
+/// use go_parse_duration::{DurationValueParser, GoDuration};
+///
+/// let cmd = Command::new("server").arg(
+///     Arg::new("timeout")
+///         .long("timeout")
+///         .value_name("DURATION")
+///         .value_parser(DurationValueParser),
+/// );
+/// let m = cmd.try_get_matches_from(["server", "--timeout", "1m30s"]).unwrap();
+/// assert_eq!(m.get_one::<GoDuration>("timeout").unwrap().to_string(), "1m30s");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DurationValueParser;
+
+impl TypedValueParser for DurationValueParser {
+    type Value = GoDuration;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<GoDuration, clap::Error> {
+        StringValueParser::new()
+            .try_map(|s| GoDuration::parse(&s))
+            .parse_ref(cmd, arg, value)
+    }
+}
+
+impl ValueParserFactory for GoDuration {
+    type Parser = DurationValueParser;
+
+    fn value_parser() -> DurationValueParser {
+        DurationValueParser
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::error::ErrorKind;
+    use clap::{value_parser, Arg, Command};
+
+    fn command() -> Command {
+        Command::new("server").arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_parser(value_parser!(GoDuration)),
+        )
+    }
+
+    #[test]
+    fn test_value_parser() {
+        let m = command()
+            .try_get_matches_from(["server", "--timeout=-1.5s"])
+            .unwrap();
+        assert_eq!(
+            m.get_one::<GoDuration>("timeout"),
+            Some(&GoDuration::from_nanos(-1500000000))
+        );
+        let err = command()
+            .try_get_matches_from(["server", "--timeout", "1d"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("unknown unit d in duration 1d"));
+    }
+}
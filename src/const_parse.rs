@@ -0,0 +1,273 @@
+// A const fn Go duration parser, for validating duration strings at compile
+// time in dur_consts! and duration!.
+//
+// It mirrors parse, except that it can't look units up in a table and its
+// errors carry no message. parse_duration_const exposes it, panicking on
+// errors.
+use crate::consts::{HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConstError {
+    Invalid,
+    MissingUnit,
+    UnknownUnit,
+    Overflow,
+}
+
+/// parse_duration_const parses a duration string like [`parse_duration`],
+/// but is a `const fn`, so it can initialize constants. It panics if the
+/// string is invalid, which is a compile error in a const context.
+///
+/// ```rust
+/// use go_parse_duration::parse_duration_const;
+///
+/// const TIMEOUT: i64 = parse_duration_const("30s");
+/// assert_eq!(TIMEOUT, 30000000000);
+/// ```
+///
+/// ```compile_fail
+/// const TIMEOUT: i64 = go_parse_duration::parse_duration_const("30 s");
+/// ```
+///
+/// # Panics
+///
+/// Panics if the string isn't a valid duration.
+///
+/// [`parse_duration`]: crate::parse_duration
+pub const fn parse_duration_const(s: &str) -> i64 {
+    match parse_const(s) {
+        Ok(d) => d,
+        Err(ConstError::Invalid) => panic!("invalid duration"),
+        Err(ConstError::MissingUnit) => panic!("missing unit in duration"),
+        Err(ConstError::UnknownUnit) => panic!("unknown unit in duration"),
+        Err(ConstError::Overflow) => panic!("duration overflows"),
+    }
+}
+
+// parse_const parses a Go duration string like parse_duration.
+pub(crate) const fn parse_const(string: &str) -> Result<i64, ConstError> {
+    let s = string.as_bytes();
+    let mut i = 0;
+    let mut d: u64 = 0; // magnitude of the duration to be returned
+    let mut neg = false;
+
+    // Consume [-+]?
+    if i < s.len() && (s[i] == b'-' || s[i] == b'+') {
+        neg = s[i] == b'-';
+        i += 1;
+    }
+    // Special case: if all that is left is "0", this is zero.
+    if i + 1 == s.len() && s[i] == b'0' {
+        return Ok(0);
+    }
+    if i == s.len() {
+        return Err(ConstError::Invalid);
+    }
+    // The largest magnitude of the duration: that of i64::MIN if negative.
+    let limit = if neg { 1 << 63 } else { i64::MAX as u64 };
+    while i < s.len() {
+        // The next character must be [0-9.]
+        if !(s[i] == b'.' || s[i].is_ascii_digit()) {
+            return Err(ConstError::Invalid);
+        }
+        // Consume [0-9]*
+        let start = i;
+        let mut v: u64 = 0;
+        while i < s.len() && s[i].is_ascii_digit() {
+            v = match v.checked_mul(10) {
+                Some(v) => v,
+                None => return Err(ConstError::Overflow),
+            };
+            v = match v.checked_add((s[i] - b'0') as u64) {
+                Some(v) => v,
+                None => return Err(ConstError::Overflow),
+            };
+            i += 1;
+        }
+        if v > limit {
+            return Err(ConstError::Overflow);
+        }
+        let pre = i != start;
+
+        // Consume (\.[0-9]*)?
+        let mut f: u64 = 0;
+        let mut scale: f64 = 1f64;
+        let mut post = false;
+        if i < s.len() && s[i] == b'.' {
+            i += 1;
+            let start = i;
+            let mut overflow = false;
+            while i < s.len() && s[i].is_ascii_digit() {
+                if !overflow {
+                    match f.checked_mul(10) {
+                        Some(y) => match y.checked_add((s[i] - b'0') as u64) {
+                            Some(y) => {
+                                f = y;
+                                scale *= 10f64;
+                            }
+                            None => overflow = true,
+                        },
+                        None => overflow = true,
+                    }
+                }
+                i += 1;
+            }
+            post = i != start;
+        }
+        if !pre && !post {
+            // no digits (e.g. ".s" or "-.s")
+            return Err(ConstError::Invalid);
+        }
+
+        // Consume unit.
+        let start = i;
+        while i < s.len() && s[i] != b'.' && !s[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == start {
+            return Err(ConstError::MissingUnit);
+        }
+        let (_, rest) = s.split_at(start);
+        let (u, _) = rest.split_at(i - start);
+        let unit: u64 = match u {
+            b"ns" => 1,
+            b"us" => MICROSECOND as u64,
+            [0xC2, 0xB5, b's'] => MICROSECOND as u64, // U+00B5 = micro symbol
+            [0xCE, 0xBC, b's'] => MICROSECOND as u64, // U+03BC = Greek letter mu
+            b"ms" => MILLISECOND as u64,
+            b"s" => SECOND as u64,
+            b"m" => MINUTE as u64,
+            b"h" => HOUR as u64,
+            _ => return Err(ConstError::UnknownUnit),
+        };
+
+        let mut c = match v.checked_mul(unit) {
+            Some(c) => c,
+            None => return Err(ConstError::Overflow),
+        };
+        if f > 0 {
+            c = match c.checked_add((f as f64 * (unit as f64 / scale)) as u64) {
+                Some(c) => c,
+                None => return Err(ConstError::Overflow),
+            };
+        }
+        d = match d.checked_add(c) {
+            Some(d) if d <= limit => d,
+            _ => return Err(ConstError::Overflow),
+        };
+    }
+    if neg {
+        // The magnitude is at most 1<<63, whose negation is i64::MIN.
+        return Ok((d as i64).wrapping_neg());
+    }
+    Ok(d as i64)
+}
+
+/// dur_consts defines `pub const` i64 durations in nanoseconds from Go
+/// duration strings, which are parsed at compile time.
+///
+/// ```rust
+/// go_parse_duration::dur_consts! {
+///     /// How long to wait for a connection.
+///     CONNECT_TIMEOUT = "5s";
+///     RETRY_BACKOFF = "250ms";
+/// }
+///
+/// assert_eq!(CONNECT_TIMEOUT, 5000000000);
+/// assert_eq!(RETRY_BACKOFF, 250000000);
+/// ```
+///
+/// An invalid duration string fails the build:
+///
+/// ```compile_fail
+/// go_parse_duration::dur_consts! {
+///     TIMEOUT = "5 seconds";
+/// }
+/// ```
+#[macro_export]
+macro_rules! dur_consts {
+    ($($(#[$attr:meta])* $name:ident = $s:expr;)*) => {
+        $(
+            $(#[$attr])*
+            pub const $name: i64 = $crate::parse_duration_const($s);
+        )*
+    };
+}
+
+/// duration evaluates to the i64 duration in nanoseconds of a Go duration
+/// string, which is parsed at compile time, so hard-coded durations cost
+/// nothing at run time and typos in them fail the build.
+///
+/// ```rust
+/// use go_parse_duration::duration;
+///
+/// let timeout = duration!("1h30m");
+/// assert_eq!(timeout, 5400000000000);
+///
+/// const BACKOFF: i64 = duration!("250ms");
+/// assert_eq!(BACKOFF, 250000000);
+/// ```
+///
+/// An invalid duration string fails the build:
+///
+/// ```compile_fail
+/// let timeout = go_parse_duration::duration!("1h30");
+/// ```
+#[macro_export]
+macro_rules! duration {
+    ($s:expr) => {{
+        const DURATION: i64 = $crate::parse_duration_const($s);
+        DURATION
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_vectors::VECTORS;
+
+    #[test]
+    fn test_parse_const() {
+        for v in VECTORS {
+            assert_eq!(parse_const(v.input).ok(), v.expected, "{}", v.input);
+        }
+        assert_eq!(parse_const("1d"), Err(ConstError::UnknownUnit));
+        assert_eq!(parse_const("1"), Err(ConstError::MissingUnit));
+        assert_eq!(parse_const("9223372036854775807ns"), Ok(i64::MAX));
+        assert_eq!(
+            parse_const("9223372036854775808ns"),
+            Err(ConstError::Overflow)
+        );
+    }
+
+    crate::dur_consts! {
+        TIMEOUT = "1m30s";
+        BACKOFF = "-1.5ms";
+    }
+
+    #[test]
+    fn test_dur_consts() {
+        assert_eq!(TIMEOUT, 90000000000);
+        assert_eq!(BACKOFF, -1500000);
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(crate::duration!("1h30m"), 5400000000000);
+        assert_eq!(crate::duration!("-2µs"), -2000);
+        assert_eq!(crate::duration!("0"), 0);
+    }
+
+    #[test]
+    fn test_parse_duration_const() {
+        const TIMEOUT: i64 = parse_duration_const("1.5h");
+        assert_eq!(TIMEOUT, 5400000000000);
+        assert_eq!(parse_duration_const("-1ns"), -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown unit in duration")]
+    fn test_parse_duration_const_panics() {
+        parse_duration_const("1d");
+    }
+}
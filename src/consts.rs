@@ -0,0 +1,53 @@
+//! The lengths of the Go duration units in nanoseconds, and the bounds of a
+//! duration, as Go's `time` package defines them.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! use go_parse_duration::consts::{MINUTE, SECOND};
+//!
+//! let timeout = 2 * MINUTE + 30 * SECOND;
+//! assert_eq!(go_parse_duration::format_duration(timeout), "2m30s");
+//! # }
+//! ```
+
+/// NANOSECOND is the length of a nanosecond, the unit of durations.
+pub const NANOSECOND: i64 = 1;
+/// MICROSECOND is the length of a microsecond in nanoseconds.
+pub const MICROSECOND: i64 = 1000 * NANOSECOND;
+/// MILLISECOND is the length of a millisecond in nanoseconds.
+pub const MILLISECOND: i64 = 1000 * MICROSECOND;
+/// SECOND is the length of a second in nanoseconds.
+pub const SECOND: i64 = 1000 * MILLISECOND;
+/// MINUTE is the length of a minute in nanoseconds.
+pub const MINUTE: i64 = 60 * SECOND;
+/// HOUR is the length of an hour in nanoseconds.
+pub const HOUR: i64 = 60 * MINUTE;
+
+/// MAX_DURATION is the longest duration, about 292 years.
+pub const MAX_DURATION: i64 = i64::MAX;
+/// MIN_DURATION is the most negative duration, about -292 years.
+pub const MIN_DURATION: i64 = i64::MIN;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::try_parse_duration;
+
+    #[test]
+    fn test_consts() {
+        assert_eq!(try_parse_duration("1ns"), Ok(NANOSECOND));
+        assert_eq!(try_parse_duration("1µs"), Ok(MICROSECOND));
+        assert_eq!(try_parse_duration("1ms"), Ok(MILLISECOND));
+        assert_eq!(try_parse_duration("1s"), Ok(SECOND));
+        assert_eq!(try_parse_duration("1m"), Ok(MINUTE));
+        assert_eq!(try_parse_duration("1h"), Ok(HOUR));
+        assert_eq!(
+            try_parse_duration("2562047h47m16.854775807s"),
+            Ok(MAX_DURATION)
+        );
+        assert_eq!(
+            try_parse_duration("-2562047h47m16.854775808s"),
+            Ok(MIN_DURATION)
+        );
+    }
+}
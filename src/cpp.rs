@@ -0,0 +1,51 @@
+//! A C++ bridge built with [cxx](https://cxx.rs), available with the `cxx`
+//! feature.
+//!
+//! The bridge exposes, in the `go_parse_duration` namespace:
+//!
+//! ```cpp
+//! int64_t parse_duration(const std::string &s); // throws rust::Error
+//! rust::String format_duration(int64_t d);
+//! ```
+//!
+//! Generate the header and glue from this file in the C++ side's build, e.g.
+//! with `cxx_build::bridge` or the `cxxbridge` command line tool.
+use cxx::CxxString;
+
+use crate::Error;
+
+#[cxx::bridge(namespace = "go_parse_duration")]
+mod ffi {
+    extern "Rust" {
+        fn parse_duration(s: &CxxString) -> Result<i64>;
+        fn format_duration(d: i64) -> String;
+    }
+}
+
+// parse_duration parses a C++ string as a Go duration string. Strings that
+// aren't valid UTF-8 are invalid durations.
+fn parse_duration(s: &CxxString) -> Result<i64, Error> {
+    match s.to_str() {
+        Ok(s) => crate::parse_duration(s),
+        Err(_) => Err(Error::invalid_syntax(&s.to_string_lossy())),
+    }
+}
+
+fn format_duration(d: i64) -> String {
+    crate::format_duration(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cxx::let_cxx_string;
+
+    #[test]
+    fn test_bridge() {
+        let_cxx_string!(s = "1h30m");
+        assert_eq!(parse_duration(&s), Ok(5400000000000));
+        let_cxx_string!(s = b"1\xffs");
+        assert_eq!(parse_duration(&s), Err(Error::invalid_syntax("1\u{FFFD}s")));
+        assert_eq!(format_duration(5400000000000), "1h30m0s");
+    }
+}
@@ -0,0 +1,213 @@
+//! Duration grammars other than Go's.
+//!
+//! Each dialect is a unit struct implementing [`DurationDialect`], so it can
+//! be picked at runtime or passed around as `&dyn DurationDialect`.
+use crate::consts::{HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{format_duration, leading_int, parse_duration, Error, MAX_MAGNITUDE};
+use core::convert::TryFrom;
+
+mod cjk;
+mod clock;
+mod ffmpeg;
+mod humantime;
+mod iso8601;
+mod nginx;
+mod numpy;
+mod pandas;
+mod postgres;
+mod prometheus;
+mod python;
+mod rails;
+mod sleep;
+mod sql;
+mod sqlite;
+mod systemd;
+mod timespan;
+mod varnish;
+
+pub use cjk::Cjk;
+pub use clock::Clock;
+pub use ffmpeg::Ffmpeg;
+pub use humantime::Humantime;
+pub use iso8601::{parse_iso8601, Iso8601};
+pub use nginx::Nginx;
+pub use numpy::Numpy;
+pub use pandas::Pandas;
+pub use postgres::PostgresInterval;
+pub use prometheus::Prometheus;
+pub use python::Python;
+pub use rails::Rails;
+pub use sleep::Sleep;
+pub use sql::SqlInterval;
+pub use sqlite::Sqlite;
+pub use systemd::Systemd;
+pub use timespan::TimeSpan;
+pub use varnish::Varnish;
+
+const DAY: i64 = 24 * HOUR;
+
+/// DurationDialect parses a duration string written in a particular grammar
+/// and returns duration in nanoseconds.
+pub trait DurationDialect {
+    fn parse(&self, s: &str) -> Result<i64, Error>;
+
+    /// format returns a duration in nanoseconds written in the dialect's
+    /// grammar.
+    ///
+    /// Dialects that can only be read return an error.
+    fn format(&self, d: i64) -> Result<String, Error> {
+        Err(Error::ParseError(format!(
+            "unsupported format for duration {}",
+            format_duration(d)
+        )))
+    }
+}
+
+/// Go is the grammar of Go's `time.ParseDuration` and `Duration.String()`,
+/// for converting to and from the other dialects.
+pub struct Go;
+
+impl DurationDialect for Go {
+    fn parse(&self, s: &str) -> Result<i64, Error> {
+        parse_duration(s)
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        Ok(format_duration(d))
+    }
+}
+
+/// convert parses a duration string written in one dialect and writes it in
+/// another.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{convert, Go, Python};
+///
+/// assert_eq!(convert("26h3m4s", &Go, &Python).unwrap(), "1 day, 2:03:04");
+/// assert_eq!(convert("0:00:01.500000", &Python, &Go).unwrap(), "1.5s");
+/// ```
+pub fn convert(
+    s: &str,
+    from: &dyn DurationDialect,
+    to: &dyn DurationDialect,
+) -> Result<String, Error> {
+    to.format(from.parse(s)?)
+}
+
+// number consumes the leading [0-9]+ from s.
+fn number(s: &str) -> Option<(i64, &str)> {
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if i == 0 {
+        return None;
+    }
+    let (v, _) = leading_int(&s[..i])?;
+    Some((v, &s[i..]))
+}
+
+// offset_in returns the offset in string of sub, which must be a slice of it.
+fn offset_in(string: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - string.as_ptr() as usize
+}
+
+// two_digits consumes exactly two leading digits from s.
+fn two_digits(s: &str) -> Option<(i64, &str)> {
+    let b = s.as_bytes();
+    if b.len() < 2 || !b[0].is_ascii_digit() || !b[1].is_ascii_digit() {
+        return None;
+    }
+    Some((i64::from((b[0] - b'0') * 10 + (b[1] - b'0')), &s[2..]))
+}
+
+// fraction_nanos consumes [0-9]{1,9} from s and returns them as nanoseconds,
+// i.e. "5" is 500000000 and "000001" is 1000.
+fn fraction_nanos(s: &str) -> Option<(i64, &str)> {
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if i == 0 || i > 9 {
+        return None;
+    }
+    let (v, _) = leading_int(&s[..i])?;
+    Some((v * 10i64.pow(9 - i as u32), &s[i..]))
+}
+
+// signed returns the duration with the given sign and magnitude, or None if
+// it doesn't fit an i64.
+fn signed(neg: bool, magnitude: u64) -> Option<i64> {
+    if !neg {
+        return i64::try_from(magnitude).ok();
+    }
+    if magnitude > MAX_MAGNITUDE {
+        return None;
+    }
+    Some((magnitude as i64).wrapping_neg())
+}
+
+// days_nanos returns days * DAY + t, or None if it overflows.
+fn days_nanos(days: i64, t: i64) -> Option<i64> {
+    let d = i128::from(days) * i128::from(DAY) + i128::from(t);
+    if d < i128::from(i64::MIN) || d > i128::from(i64::MAX) {
+        return None;
+    }
+    Some(d as i64)
+}
+
+// format_clock formats t, which is in 0..DAY, as H:MM:SS followed by the
+// given number of fraction digits, if the fraction isn't zero. The hours are
+// zero-padded to two digits if pad is set.
+fn format_clock(t: i64, pad: bool, digits: u32) -> String {
+    let (h, m, sec) = (t / HOUR, t / MINUTE % 60, t / SECOND % 60);
+    let mut s = if pad {
+        format!("{:02}:{:02}:{:02}", h, m, sec)
+    } else {
+        format!("{}:{:02}:{:02}", h, m, sec)
+    };
+    let frac = t % SECOND / 10i64.pow(9 - digits);
+    if frac != 0 {
+        s.push_str(&format!(".{:0width$}", frac, width = digits as usize));
+    }
+    s
+}
+
+// clock parses a whole H+:MM:SS(.[0-9]{1,9})? string and returns duration in
+// nanoseconds.
+fn clock(s: &str) -> Option<i64> {
+    let (h, s) = number(s)?;
+    let s = s.strip_prefix(':')?;
+    let (m, s) = two_digits(s)?;
+    let s = s.strip_prefix(':')?;
+    let (sec, s) = two_digits(s)?;
+    if m >= 60 || sec >= 60 {
+        return None;
+    }
+    let ns = match s.strip_prefix('.') {
+        Some(s) => match fraction_nanos(s)? {
+            (ns, "") => ns,
+            _ => return None,
+        },
+        None if s.is_empty() => 0,
+        None => return None,
+    };
+    h.checked_mul(HOUR)?
+        .checked_add(m * MINUTE + sec * SECOND + ns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() -> Result<(), Error> {
+        assert_eq!(convert("26h3m4s", &Go, &Python)?, "1 day, 2:03:04");
+        assert_eq!(convert("-1 days +23:59:59", &Pandas, &Go)?, "-1s");
+        assert_eq!(convert("2.hours", &Rails, &Numpy)?, "2 hours");
+        assert_eq!(
+            convert("1.5s", &Varnish, &Pandas)?,
+            "0 days 00:00:01.500000"
+        );
+        assert_eq!(
+            convert("1h", &Go, &Rails).unwrap_err(),
+            Error::ParseError(String::from("unsupported format for duration 1h0m0s")),
+        );
+        assert!(convert("1x", &Go, &Python).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use super::{DurationDialect, DAY, HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{parse, Error, Syntax};
+
+/// Cjk parses durations written with Chinese or Japanese unit characters,
+/// such as "1时30分", "45秒" or "2時間15分".
+///
+/// The syntax is Go's, with the units replaced: "天", "日" for days, "周",
+/// "週", "週間" for weeks, "时", "時", "小时", "小時", "時間" for hours, "分",
+/// "分钟", "分鐘" for minutes, "秒" for seconds, and "毫秒", "微秒",
+/// "纳秒", "納秒" for the sub-second units. Go's ASCII units are accepted as
+/// well.
+pub struct Cjk;
+
+impl DurationDialect for Cjk {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        parse(string, cjk_unit, &Syntax::GO).map_err(|e| e.into_error(string))
+    }
+}
+
+// cjk_unit returns the length of a CJK (or Go) unit in nanoseconds.
+fn cjk_unit(u: &str) -> Option<i64> {
+    let ns = match u {
+        "周" | "週" | "週間" | "星期" => 7 * DAY,
+        "天" | "日" => DAY,
+        "时" | "時" | "小时" | "小時" | "時間" => HOUR,
+        "分" | "分钟" | "分鐘" => MINUTE,
+        "秒" | "秒钟" | "秒鐘" => SECOND,
+        "毫秒" | "ミリ秒" => MILLISECOND,
+        "微秒" | "マイクロ秒" => MICROSECOND,
+        "纳秒" | "納秒" | "ナノ秒" => 1,
+        _ => return crate::unit_nanos(u),
+    };
+    Some(ns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cjk() -> Result<(), Error> {
+        assert_eq!(Cjk.parse("1时30分")?, 5400000000000);
+        assert_eq!(Cjk.parse("45秒")?, 45000000000);
+        assert_eq!(Cjk.parse("2時間15分")?, 8100000000000);
+        assert_eq!(Cjk.parse("1天2小时")?, 93600000000000);
+        assert_eq!(Cjk.parse("1週間")?, 604800000000000);
+        assert_eq!(Cjk.parse("1.5秒")?, 1500000000);
+        assert_eq!(Cjk.parse("-500毫秒")?, -500000000);
+        assert_eq!(Cjk.parse("1h30分")?, 5400000000000);
+        assert_eq!(
+            Cjk.parse("3年").unwrap_err(),
+            Error::unknown_unit("年", "3年"),
+        );
+        assert!(Cjk.parse("秒").is_err());
+        Ok(())
+    }
+}
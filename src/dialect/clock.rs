@@ -0,0 +1,150 @@
+use super::{fraction_nanos, number, two_digits, DurationDialect, HOUR, MINUTE, SECOND};
+use crate::Error;
+
+/// Clock parses and formats durations written as clock times, such as
+/// "01:30:15.250" or "90:00", as stopwatches and logs print them.
+///
+/// A clock time is an optional sign followed by hours, minutes and seconds
+/// separated by colons, where the seconds can have up to nine fraction
+/// digits. The first field can be any number of digits and the others are
+/// two digits below 60. Whether a time with two fields, such as "90:00", is
+/// minutes and seconds or hours and minutes depends on the constant used.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{Clock, DurationDialect};
+///
+/// assert_eq!(Clock::MINUTES_SECONDS.parse("01:30:15.250"), Ok(5415250000000));
+/// assert_eq!(Clock::MINUTES_SECONDS.parse("90:00"), Ok(5400000000000));
+/// assert_eq!(Clock::HOURS_MINUTES.parse("90:00"), Ok(324000000000000));
+/// assert_eq!(Clock::MINUTES_SECONDS.format(5415250000000).unwrap(), "01:30:15.250");
+/// ```
+pub struct Clock {
+    hours_minutes: bool,
+}
+
+impl Clock {
+    /// MINUTES_SECONDS reads two fields as minutes and seconds, as in
+    /// "4:33.5".
+    pub const MINUTES_SECONDS: Clock = Clock {
+        hours_minutes: false,
+    };
+
+    /// HOURS_MINUTES reads two fields as hours and minutes, as in "2:30".
+    pub const HOURS_MINUTES: Clock = Clock {
+        hours_minutes: true,
+    };
+}
+
+impl DurationDialect for Clock {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let (neg, s) = match string.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let (first, s) = number(s).ok_or_else(invalid)?;
+        let s = s.strip_prefix(':').ok_or_else(invalid)?;
+        let (second, s) = two_digits(s).ok_or_else(invalid)?;
+        if second >= 60 {
+            return Err(invalid());
+        }
+        // seconds is whether the last field is seconds, which can have a
+        // fraction.
+        let ((h, m, sec), s, seconds) = match s.strip_prefix(':') {
+            Some(s) => match two_digits(s).ok_or_else(invalid)? {
+                (third, s) if third < 60 => ((first, second, third), s, true),
+                _ => return Err(invalid()),
+            },
+            None if self.hours_minutes => ((first, second, 0), s, false),
+            None => ((0, first, second), s, true),
+        };
+        let ns = match s.strip_prefix('.') {
+            Some(s) if seconds => match fraction_nanos(s) {
+                Some((ns, "")) => ns,
+                _ => return Err(invalid()),
+            },
+            None if s.is_empty() => 0,
+            _ => return Err(invalid()),
+        };
+        let d = h
+            .checked_mul(HOUR)
+            .and_then(|d| m.checked_mul(MINUTE)?.checked_add(d))
+            .and_then(|d| d.checked_add(sec * SECOND + ns))
+            .ok_or_else(|| Error::overflow(string))?;
+        Ok(if neg { -d } else { d })
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        let sign = if d < 0 { "-" } else { "" };
+        let u = d.unsigned_abs();
+        let (hour, minute, second) = (HOUR as u64, MINUTE as u64, SECOND as u64);
+        let mut s = format!(
+            "{}{:02}:{:02}:{:02}",
+            sign,
+            u / hour,
+            u / minute % 60,
+            u / second % 60
+        );
+        let frac = u % second;
+        if frac != 0 {
+            let digits = if frac.is_multiple_of(1000000) {
+                3
+            } else if frac.is_multiple_of(1000) {
+                6
+            } else {
+                9
+            };
+            let frac = frac / 10u64.pow(9 - digits);
+            s.push_str(&format!(".{:0width$}", frac, width = digits as usize));
+        }
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock() -> Result<(), Error> {
+        let ms = Clock::MINUTES_SECONDS;
+        let hm = Clock::HOURS_MINUTES;
+        assert_eq!(ms.parse("01:30:15.250")?, 5415250000000);
+        assert_eq!(ms.parse("1:30:15")?, 5415000000000);
+        assert_eq!(ms.parse("90:00")?, 5400000000000);
+        assert_eq!(ms.parse("4:33.5")?, 273500000000);
+        assert_eq!(ms.parse("-0:01.000000001")?, -1000000001);
+        assert_eq!(ms.parse("100:00:00")?, 360000000000000);
+        assert_eq!(hm.parse("90:00")?, 324000000000000);
+        assert_eq!(hm.parse("2:30")?, 9000000000000);
+        assert_eq!(hm.parse("01:30:15.250")?, 5415250000000);
+        assert_eq!(hm.parse("1:30:00.5")?, 5400500000000);
+        for s in [
+            "", "90", "1:60", "1:2", "1:30:60", "1:30:", ":30", "1:30.", "1:30:00x",
+        ] {
+            assert!(ms.parse(s).is_err(), "{}", s);
+        }
+        assert!(hm.parse("2:30.5").is_err());
+        assert_eq!(
+            ms.parse("9999999:00:00").unwrap_err(),
+            Error::overflow("9999999:00:00")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clock_format() -> Result<(), Error> {
+        let ms = Clock::MINUTES_SECONDS;
+        assert_eq!(ms.format(0)?, "00:00:00");
+        assert_eq!(ms.format(5415250000000)?, "01:30:15.250");
+        assert_eq!(ms.format(-1000001)?, "-00:00:00.001000001");
+        assert_eq!(ms.format(360000001000)?, "00:06:00.000001");
+        assert_eq!(ms.format(360000000000000)?, "100:00:00");
+        for &d in &[1, -1500000000, i64::MAX, i64::MIN + 1] {
+            assert_eq!(ms.parse(&ms.format(d)?)?, d);
+            assert_eq!(Clock::HOURS_MINUTES.parse(&ms.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
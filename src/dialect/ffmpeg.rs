@@ -0,0 +1,163 @@
+use super::{number, DurationDialect, HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{format_duration, Error};
+
+/// Ffmpeg parses and formats time durations as ffmpeg's `-ss` and `-t`
+/// options take them, such as "01:30:15.5", "12:30", "1.5" or "250ms".
+///
+/// A duration is an optional "-" followed by either `[HH:]MM:SS[.m...]`,
+/// where the minutes and seconds are one or two digits below 60, or
+/// `S+[.m...]` with an optional unit "s", "ms" or "us". As in ffmpeg,
+/// durations have microsecond precision: fraction digits past it are
+/// dropped, as is any fraction of microseconds. Durations are formatted
+/// as ffprobe's `-sexagesimal` option writes them, as in "0:01:30.500000".
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Ffmpeg};
+///
+/// assert_eq!(Ffmpeg.parse("01:30:15.5"), Ok(5415500000000));
+/// assert_eq!(Ffmpeg.parse("250ms"), Ok(250000000));
+/// assert_eq!(Ffmpeg.format(90500000000).unwrap(), "0:01:30.500000");
+/// ```
+pub struct Ffmpeg;
+
+impl DurationDialect for Ffmpeg {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+        let overflow = || Error::overflow(string);
+
+        let (neg, s) = match string.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, string),
+        };
+        let (v, s) = number(s).ok_or_else(invalid)?;
+        let d = if s.starts_with(':') {
+            let mut fields = vec![v];
+            let mut s = s;
+            while let Some(rest) = s.strip_prefix(':') {
+                let (v, rest) = sexagesimal(rest).ok_or_else(invalid)?;
+                fields.push(v);
+                s = rest;
+            }
+            let (h, m, sec) = match fields[..] {
+                [m, sec] if m < 60 => (0, m, sec),
+                [h, m, sec] => (h, m, sec),
+                _ => return Err(invalid()),
+            };
+            let (us, s) = micros(s);
+            if !s.is_empty() {
+                return Err(invalid());
+            }
+            h.checked_mul(HOUR)
+                .and_then(|d| d.checked_add(m * MINUTE + sec * SECOND + us * MICROSECOND))
+                .ok_or_else(overflow)?
+        } else {
+            let (us, s) = micros(s);
+            let (unit, us) = match s {
+                "" | "s" => (SECOND, us),
+                "ms" => (MILLISECOND, us / 1000),
+                "us" => (MICROSECOND, 0),
+                _ => return Err(invalid()),
+            };
+            v.checked_mul(unit)
+                .and_then(|d| d.checked_add(us * MICROSECOND))
+                .ok_or_else(overflow)?
+        };
+        Ok(if neg { -d } else { d })
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d % 1000 != 0 {
+            return Err(Error::ParseError(format!(
+                "unsupported format for duration {}",
+                format_duration(d)
+            )));
+        }
+        let sign = if d < 0 { "-" } else { "" };
+        let u = d.unsigned_abs();
+        let (hour, minute, second) = (HOUR as u64, MINUTE as u64, SECOND as u64);
+        Ok(format!(
+            "{}{}:{:02}:{:02}.{:06}",
+            sign,
+            u / hour,
+            u / minute % 60,
+            u / second % 60,
+            u % second / 1000
+        ))
+    }
+}
+
+// sexagesimal consumes the one or two leading digits of a minutes or seconds
+// field from s, which must be below 60.
+fn sexagesimal(s: &str) -> Option<(i64, &str)> {
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if i == 0 || i > 2 {
+        return None;
+    }
+    let (v, _) = number(&s[..i])?;
+    if v >= 60 {
+        return None;
+    }
+    Some((v, &s[i..]))
+}
+
+// micros consumes an optional .[0-9]* from s and returns the first six
+// digits as microseconds, i.e. ".5" is 500000.
+fn micros(s: &str) -> (i64, &str) {
+    let s = match s.strip_prefix('.') {
+        Some(s) => s,
+        None => return (0, s),
+    };
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let mut us = 0;
+    let mut n = 100000;
+    for b in s[..i].bytes().take(6) {
+        us += n * i64::from(b - b'0');
+        n /= 10;
+    }
+    (us, &s[i..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffmpeg() -> Result<(), Error> {
+        assert_eq!(Ffmpeg.parse("01:30:15.5")?, 5415500000000);
+        assert_eq!(Ffmpeg.parse("100:00:00")?, 360000000000000);
+        assert_eq!(Ffmpeg.parse("1:30")?, 90000000000);
+        assert_eq!(Ffmpeg.parse("12:30")?, 750000000000);
+        assert_eq!(Ffmpeg.parse("-0:1.25")?, -1250000000);
+        assert_eq!(Ffmpeg.parse("90")?, 90000000000);
+        assert_eq!(Ffmpeg.parse("1.5")?, 1500000000);
+        assert_eq!(Ffmpeg.parse("1.5s")?, 1500000000);
+        assert_eq!(Ffmpeg.parse("250ms")?, 250000000);
+        assert_eq!(Ffmpeg.parse("1.2345ms")?, 1234000);
+        assert_eq!(Ffmpeg.parse("5us")?, 5000);
+        assert_eq!(Ffmpeg.parse("5.7us")?, 5000);
+        assert_eq!(Ffmpeg.parse("-2.")?, -2000000000);
+        assert_eq!(Ffmpeg.parse("0.1234567")?, 123456000);
+        for s in [
+            "", "-", ".5", "1:60", "60:00", "1:2:3:4", "1:30s", "5ns", "1m", "+5", "1:", "1:123",
+        ] {
+            assert!(Ffmpeg.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(
+            Ffmpeg.parse("9999999:00:00").unwrap_err(),
+            Error::overflow("9999999:00:00")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_ffmpeg_format() -> Result<(), Error> {
+        assert_eq!(Ffmpeg.format(0)?, "0:00:00.000000");
+        assert_eq!(Ffmpeg.format(90500000000)?, "0:01:30.500000");
+        assert_eq!(Ffmpeg.format(-5415000001000)?, "-1:30:15.000001");
+        assert!(Ffmpeg.format(1).is_err());
+        for &d in &[1000, -1500000000, 360000000000000, i64::MAX / 1000 * 1000] {
+            assert_eq!(Ffmpeg.parse(&Ffmpeg.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use super::{offset_in, DurationDialect, DAY, HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{format_duration, Error};
+
+/// Humantime parses and formats durations as the `humantime` crate does,
+/// such as "2days 3hours", "1y 2months" or "2h37min", for projects moving
+/// off it.
+///
+/// A humantime duration is a run of whole numbers, each followed by a unit,
+/// with optional whitespace between them. The units are the short and
+/// English names of nanoseconds up to years, where a month is 30.44 days
+/// and a year 365.25 days. There are no fractions or signs.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Humantime};
+///
+/// assert_eq!(Humantime.parse("2days 3hours"), Ok(183600000000000));
+/// assert_eq!(Humantime.format(183600000000000).unwrap(), "2days 3h");
+/// ```
+pub struct Humantime;
+
+impl DurationDialect for Humantime {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+        let overflow = || Error::overflow(string);
+
+        let mut s = string.trim_start();
+        if s.is_empty() {
+            return Err(invalid());
+        }
+        let mut d: i64 = 0;
+        while !s.is_empty() {
+            if !s.starts_with(|c: char| c.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            // As in humantime, whitespace between the digits of a number is
+            // skipped, so "1 0s" is ten seconds.
+            let i = s
+                .find(|c: char| !c.is_ascii_digit() && !c.is_whitespace())
+                .unwrap_or(s.len());
+            let mut v: i64 = 0;
+            for c in s[..i].bytes().filter(u8::is_ascii_digit) {
+                v = v
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(i64::from(c - b'0')))
+                    .ok_or_else(overflow)?;
+            }
+            s = &s[i..];
+            let i = s
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(s.len());
+            let u = &s[..i];
+            s = &s[i..];
+            if !s.is_empty() && !s.starts_with(|c: char| c.is_ascii_digit() || c.is_whitespace()) {
+                return Err(invalid());
+            }
+            s = s.trim_start();
+            let unit = match u {
+                "nanos" | "nsec" | "ns" => 1,
+                "usec" | "us" => MICROSECOND,
+                "millis" | "msec" | "ms" => MILLISECOND,
+                "seconds" | "second" | "secs" | "sec" | "s" => SECOND,
+                "minutes" | "minute" | "min" | "mins" | "m" => MINUTE,
+                "hours" | "hour" | "hr" | "hrs" | "h" => HOUR,
+                "days" | "day" | "d" => DAY,
+                "weeks" | "week" | "w" => 7 * DAY,
+                "months" | "month" | "M" => MONTH,
+                "years" | "year" | "y" => YEAR,
+                "" => {
+                    return Err(Error::missing_unit(string));
+                }
+                _ => {
+                    return Err(Error::unknown_unit_at(u, string, offset_in(string, u)));
+                }
+            };
+            d = v
+                .checked_mul(unit)
+                .and_then(|v| d.checked_add(v))
+                .ok_or_else(overflow)?;
+        }
+        Ok(d)
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d < 0 {
+            return Err(Error::ParseError(format!(
+                "negative duration {} in humantime format",
+                format_duration(d)
+            )));
+        }
+        if d == 0 {
+            return Ok(String::from("0s"));
+        }
+        let mut parts = Vec::new();
+        let mut rest = d;
+        for &(unit, singular, plural) in &UNITS {
+            let v = rest / unit;
+            rest %= unit;
+            if v == 1 {
+                parts.push(format!("{}{}", v, singular));
+            } else if v != 0 {
+                parts.push(format!("{}{}", v, plural));
+            }
+        }
+        Ok(parts.join(" "))
+    }
+}
+
+// MONTH and YEAR are humantime's average month and year, 30.44 and 365.25
+// days.
+const MONTH: i64 = 2630016 * SECOND;
+const YEAR: i64 = 31557600 * SECOND;
+
+// UNITS lists the units Humantime formats with, largest first, with their
+// singular and plural names.
+const UNITS: [(i64, &str, &str); 9] = [
+    (YEAR, "year", "years"),
+    (MONTH, "month", "months"),
+    (DAY, "day", "days"),
+    (HOUR, "h", "h"),
+    (MINUTE, "m", "m"),
+    (SECOND, "s", "s"),
+    (MILLISECOND, "ms", "ms"),
+    (MICROSECOND, "us", "us"),
+    (1, "ns", "ns"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_humantime() -> Result<(), Error> {
+        assert_eq!(Humantime.parse("2days 3hours")?, 183600000000000);
+        assert_eq!(Humantime.parse("1y 2months")?, 36817632000000000);
+        assert_eq!(Humantime.parse("2h37min")?, 9420000000000);
+        assert_eq!(Humantime.parse(" 1hour 12min 5s ")?, 4325000000000);
+        assert_eq!(Humantime.parse("5 s")?, 5000000000);
+        assert_eq!(Humantime.parse("1 0s")?, 10000000000);
+        assert_eq!(Humantime.parse("1w 1ms 1us 1ns")?, 604800001001001);
+        assert_eq!(Humantime.parse("0s")?, 0);
+        assert_eq!(Humantime.parse("5").unwrap_err(), Error::missing_unit("5"));
+        assert_eq!(
+            Humantime.parse("5mo").unwrap_err(),
+            Error::unknown_unit("mo", "5mo"),
+        );
+        assert_eq!(
+            Humantime.parse("5min 5mi").unwrap_err(),
+            Error::unknown_unit_at("mi", "5min 5mi", 6),
+        );
+        for s in ["", " ", "-1s", "1.5s", "s", "1s,2s", "1µs"] {
+            assert!(Humantime.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(
+            Humantime.parse("300years").unwrap_err(),
+            Error::overflow("300years")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_humantime_format() -> Result<(), Error> {
+        assert_eq!(Humantime.format(0)?, "0s");
+        assert_eq!(Humantime.format(183600000000000)?, "2days 3h");
+        assert_eq!(Humantime.format(36817632000000000)?, "1year 2months");
+        assert_eq!(Humantime.format(86401500000000)?, "1day 1s 500ms");
+        assert!(Humantime.format(-1).is_err());
+        for &d in &[1, 1500000000, 5400000000000, i64::MAX] {
+            assert_eq!(Humantime.parse(&Humantime.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
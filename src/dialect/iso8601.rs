@@ -0,0 +1,181 @@
+use super::{signed, DurationDialect, DAY, HOUR, MINUTE, SECOND};
+use crate::{component_magnitude, leading_fraction, leading_int, Error};
+
+/// Iso8601 parses ISO 8601 durations of the form `PnWnDTnHnMnS`, such as
+/// "PT1H30M", "P1DT2H" or "PT0.5S".
+///
+/// Any component may have a fraction, written with `.` or `,`. A day is 24
+/// hours. Years and months have no fixed length and are rejected. A leading
+/// `-` negates the whole duration.
+///
+/// Durations format with hours as the largest unit, as Java's
+/// `Duration.toString()` does, e.g. "PT26H3M4S".
+pub struct Iso8601;
+
+/// parse_iso8601 parses an ISO 8601 duration such as "PT1H30M" and returns
+/// duration in nanoseconds. It's a shorthand for `Iso8601.parse(s)`.
+///
+/// ```rust
+/// use go_parse_duration::parse_iso8601;
+///
+/// assert_eq!(parse_iso8601("PT1H30M"), Ok(5400000000000));
+/// assert_eq!(parse_iso8601("P1DT0.5S"), Ok(86400500000000));
+/// ```
+pub fn parse_iso8601(s: &str) -> Result<i64, Error> {
+    Iso8601.parse(s)
+}
+
+// DATE and TIME list the designators of each part of a duration, in the
+// order they must appear.
+const DATE: [(u8, i64); 4] = [(b'Y', 0), (b'M', 0), (b'W', 7 * DAY), (b'D', DAY)];
+const TIME: [(u8, i64); 3] = [(b'H', HOUR), (b'M', MINUTE), (b'S', SECOND)];
+
+impl DurationDialect for Iso8601 {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let (neg, s) = match string.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let s = s.strip_prefix('P').ok_or_else(invalid)?;
+        let (date, time) = match s.find('T') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+        if date.is_empty() && time.is_none() {
+            return Err(invalid());
+        }
+        let mut d = part(date, &DATE, string)?;
+        if let Some(time) = time {
+            if time.is_empty() {
+                return Err(invalid());
+            }
+            d = d
+                .checked_add(part(time, &TIME, string)?)
+                .ok_or_else(|| Error::overflow(string))?;
+        }
+        signed(neg, d).ok_or_else(|| Error::overflow(string))
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d == 0 {
+            return Ok(String::from("PT0S"));
+        }
+        let mut s = String::from(if d < 0 { "-PT" } else { "PT" });
+        let u = d.unsigned_abs();
+        let (h, m, sec, ns) = (
+            u / HOUR as u64,
+            u / MINUTE as u64 % 60,
+            u / SECOND as u64 % 60,
+            u % SECOND as u64,
+        );
+        if h > 0 {
+            s.push_str(&format!("{}H", h));
+        }
+        if m > 0 {
+            s.push_str(&format!("{}M", m));
+        }
+        if sec > 0 || ns > 0 {
+            s.push_str(&sec.to_string());
+            if ns > 0 {
+                let frac = format!("{:09}", ns);
+                s.push('.');
+                s.push_str(frac.trim_end_matches('0'));
+            }
+            s.push('S');
+        }
+        Ok(s)
+    }
+}
+
+// part parses the components of the date or time part of a duration, whose
+// designators and their lengths are listed in units, and returns its
+// magnitude in nanoseconds.
+fn part(mut s: &str, units: &[(u8, i64)], string: &str) -> Result<u64, Error> {
+    let invalid = || Error::invalid_syntax(string);
+
+    let mut d: u64 = 0;
+    let mut next = 0; // index in units of the next designator allowed
+    while !s.is_empty() {
+        let pl = s.len();
+        let (v, rest) = leading_int(s).ok_or_else(invalid)?;
+        if rest.len() == pl {
+            return Err(invalid());
+        }
+        s = rest;
+        let (f, scale) = match s.strip_prefix(|c| c == '.' || c == ',') {
+            Some(rest) => {
+                let (f, scale, rest) = leading_fraction(rest);
+                s = rest;
+                (f, scale)
+            }
+            None => (0, 1f64),
+        };
+        let c = *s
+            .as_bytes()
+            .first()
+            .ok_or_else(|| Error::missing_unit(string))?;
+        let i = match units[next..].iter().position(|&(u, _)| u == c) {
+            Some(i) => next + i,
+            None => return Err(invalid()),
+        };
+        let unit = units[i].1;
+        if unit == 0 {
+            return Err(Error::ParseError(format!(
+                "unsupported unit {} in duration {}",
+                c as char, string
+            )));
+        }
+        next = i + 1;
+        s = &s[1..];
+        d = component_magnitude(v as u64, f, scale, unit, false)
+            .and_then(|v| d.checked_add(v))
+            .ok_or_else(|| Error::overflow(string))?;
+    }
+    Ok(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso8601() -> Result<(), Error> {
+        assert_eq!(Iso8601.parse("PT1H30M")?, 5400000000000);
+        assert_eq!(Iso8601.parse("P1DT2H")?, 93600000000000);
+        assert_eq!(Iso8601.parse("PT0.5S")?, 500000000);
+        assert_eq!(Iso8601.parse("PT1,5S")?, 1500000000);
+        assert_eq!(Iso8601.parse("P2W")?, 1209600000000000);
+        assert_eq!(Iso8601.parse("PT36H")?, 129600000000000);
+        assert_eq!(Iso8601.parse("-PT1M")?, -60000000000);
+        assert_eq!(Iso8601.parse("P0D")?, 0);
+        assert_eq!(
+            Iso8601.parse("P1Y").unwrap_err(),
+            Error::ParseError(String::from("unsupported unit Y in duration P1Y")),
+        );
+        assert!(Iso8601.parse("P1M").is_err());
+        assert!(Iso8601.parse("P").is_err());
+        assert!(Iso8601.parse("PT").is_err());
+        assert!(Iso8601.parse("P1DT").is_err());
+        assert!(Iso8601.parse("PT1S1M").is_err());
+        assert!(Iso8601.parse("PT1H1H").is_err());
+        assert!(Iso8601.parse("P1H").is_err());
+        assert!(Iso8601.parse("PT1").is_err());
+        assert!(Iso8601.parse("1H").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_iso8601_format() -> Result<(), Error> {
+        assert_eq!(Iso8601.format(5400000000000)?, "PT1H30M");
+        assert_eq!(Iso8601.format(93784000000000)?, "PT26H3M4S");
+        assert_eq!(Iso8601.format(-1500000000)?, "-PT1.5S");
+        assert_eq!(Iso8601.format(1)?, "PT0.000000001S");
+        assert_eq!(Iso8601.format(0)?, "PT0S");
+        for &d in &[1, -1, 3600000000000, 1234567890123456789, i64::MIN] {
+            assert_eq!(Iso8601.parse(&Iso8601.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use super::{number, offset_in, DurationDialect, DAY, HOUR, MILLISECOND, MINUTE, SECOND};
+use crate::Error;
+
+/// Nginx parses nginx time values, such as "30d", "1M", "12h30m", "1h 30m"
+/// or "60", so configs written for nginx can be read unchanged.
+///
+/// A time value is a run of whole numbers with the units "y", "M", "w",
+/// "d", "h", "m", "s" and "ms", largest first and each at most once, and
+/// optionally separated by whitespace. A month is 30 days and a year 365.
+/// A last number without a unit is in seconds. There are no fractions or
+/// signs.
+///
+/// Caddy's durations are Go's with days, which
+/// [`DurationParser::allow_days`](crate::DurationParser::allow_days)
+/// accepts.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Nginx};
+///
+/// assert_eq!(Nginx.parse("12h30m"), Ok(45000000000000));
+/// assert_eq!(Nginx.parse("60"), Ok(60000000000));
+/// ```
+pub struct Nginx;
+
+impl DurationDialect for Nginx {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let mut s = string.trim_matches(|c: char| c.is_ascii_whitespace());
+        if s.is_empty() {
+            return Err(invalid());
+        }
+        let mut d: i64 = 0;
+        // next is the index in UNITS of the largest unit that may come next.
+        let mut next = 0;
+        while !s.is_empty() {
+            let (v, rest) = number(s).ok_or_else(invalid)?;
+            let i = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let (u, rest) = rest.split_at(i);
+            s = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            // A number without a unit is the last component, in seconds.
+            let u = match u {
+                "" if s.is_empty() => "s",
+                "" => return Err(invalid()),
+                u => u,
+            };
+            let i = match UNITS.iter().position(|&(_, name)| name == u) {
+                Some(i) if i >= next => i,
+                Some(_) => return Err(invalid()),
+                None => return Err(Error::unknown_unit_at(u, string, offset_in(string, u))),
+            };
+            next = i + 1;
+            d = v
+                .checked_mul(UNITS[i].0)
+                .and_then(|v| d.checked_add(v))
+                .ok_or_else(|| Error::overflow(string))?;
+        }
+        Ok(d)
+    }
+}
+
+// UNITS lists the units of nginx time values in the order they must be
+// written.
+const UNITS: [(i64, &str); 8] = [
+    (365 * DAY, "y"),
+    (30 * DAY, "M"),
+    (7 * DAY, "w"),
+    (DAY, "d"),
+    (HOUR, "h"),
+    (MINUTE, "m"),
+    (SECOND, "s"),
+    (MILLISECOND, "ms"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nginx() -> Result<(), Error> {
+        assert_eq!(Nginx.parse("30d")?, 2592000000000000);
+        assert_eq!(Nginx.parse("1M")?, 2592000000000000);
+        assert_eq!(Nginx.parse("1y")?, 31536000000000000);
+        assert_eq!(Nginx.parse("12h30m")?, 45000000000000);
+        assert_eq!(Nginx.parse("1h 30m")?, 5400000000000);
+        assert_eq!(Nginx.parse("60")?, 60000000000);
+        assert_eq!(Nginx.parse("1m 30")?, 90000000000);
+        assert_eq!(Nginx.parse("1s 500ms")?, 1500000000);
+        assert_eq!(Nginx.parse("2w")?, 1209600000000000);
+        assert_eq!(Nginx.parse(" 0 ")?, 0);
+        assert_eq!(
+            Nginx.parse("5us").unwrap_err(),
+            Error::unknown_unit("us", "5us"),
+        );
+        for s in ["", "-1s", "1.5s", "30m1h", "1m1m", "10 5s", "1s 30", "h"] {
+            assert!(Nginx.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(Nginx.parse("300y").unwrap_err(), Error::overflow("300y"));
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use super::{
+    offset_in, signed, DurationDialect, DAY, HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND,
+};
+use crate::{component_magnitude, scan, Error};
+
+/// Numpy parses NumPy `timedelta64` values, either in their `str` form such
+/// as "5 seconds" or in their `repr` form such as "numpy.timedelta64(5,'s')".
+///
+/// Years and months have no fixed length in NumPy, so the `Y` and `M` units
+/// (and `NaT`) are rejected.
+pub struct Numpy;
+
+impl DurationDialect for Numpy {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let repr = string
+            .strip_prefix("numpy.timedelta64(")
+            .or_else(|| string.strip_prefix("np.timedelta64("));
+        let (v, u) = match repr {
+            Some(s) => {
+                let s = s.strip_suffix("')").ok_or_else(invalid)?;
+                let i = s.find(",'").ok_or_else(invalid)?;
+                (&s[..i], &s[i + 2..])
+            }
+            None => {
+                let i = string.find(' ').ok_or_else(invalid)?;
+                (&string[..i], &string[i + 1..])
+            }
+        };
+        let (neg, v) = match v.strip_prefix('-') {
+            Some(v) => (true, v),
+            None => (false, v),
+        };
+        let v = match scan::leading_int(v) {
+            Some((x, i)) if i > 0 && i == v.len() => x,
+            _ => return Err(invalid()),
+        };
+        let unit = match u {
+            "W" | "weeks" => 7 * DAY,
+            "D" | "days" => DAY,
+            "h" | "hours" => HOUR,
+            "m" | "minutes" => MINUTE,
+            "s" | "seconds" => SECOND,
+            "ms" | "milliseconds" => MILLISECOND,
+            "us" | "microseconds" => MICROSECOND,
+            "ns" | "nanoseconds" => 1,
+            _ => {
+                return Err(Error::unknown_unit_at(u, string, offset_in(string, u)));
+            }
+        };
+        component_magnitude(v, 0, 1f64, unit, false)
+            .and_then(|d| signed(neg, d))
+            .ok_or_else(|| Error::overflow(string))
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d == 0 {
+            return Ok(String::from("0 seconds"));
+        }
+        let &(unit, name) = UNITS.iter().find(|&&(unit, _)| d % unit == 0).unwrap();
+        Ok(format!("{} {}", d / unit, name))
+    }
+}
+
+// UNITS lists the units Numpy formats with, largest first.
+const UNITS: [(i64, &str); 8] = [
+    (7 * DAY, "weeks"),
+    (DAY, "days"),
+    (HOUR, "hours"),
+    (MINUTE, "minutes"),
+    (SECOND, "seconds"),
+    (MILLISECOND, "milliseconds"),
+    (MICROSECOND, "microseconds"),
+    (1, "nanoseconds"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numpy() -> Result<(), Error> {
+        assert_eq!(Numpy.parse("5 seconds")?, 5000000000);
+        assert_eq!(Numpy.parse("-3 hours")?, -10800000000000);
+        assert_eq!(Numpy.parse("250 milliseconds")?, 250000000);
+        assert_eq!(Numpy.parse("numpy.timedelta64(5,'s')")?, 5000000000);
+        assert_eq!(Numpy.parse("np.timedelta64(2,'D')")?, 172800000000000);
+        assert_eq!(
+            Numpy.parse("numpy.timedelta64(1,'Y')").unwrap_err(),
+            Error::unknown_unit("Y", "numpy.timedelta64(1,'Y')"),
+        );
+        assert!(Numpy.parse("NaT").is_err());
+        assert!(Numpy.parse("1.5 seconds").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_numpy_format() -> Result<(), Error> {
+        assert_eq!(Numpy.format(5000000000)?, "5 seconds");
+        assert_eq!(Numpy.format(-10800000000000)?, "-3 hours");
+        assert_eq!(Numpy.format(1500000000)?, "1500 milliseconds");
+        assert_eq!(Numpy.format(0)?, "0 seconds");
+        for &d in &[1, -1, 604800000000000, i64::MAX, i64::MIN] {
+            assert_eq!(Numpy.parse(&Numpy.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
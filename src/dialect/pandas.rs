@@ -0,0 +1,65 @@
+use super::{clock, days_nanos, format_clock, DurationDialect, DAY};
+use crate::Error;
+
+/// Pandas parses the string form of a pandas `Timedelta`, such as
+/// "1 days 00:00:05", "0 days 00:00:00.001000" or "-1 days +23:59:59".
+///
+/// As in pandas, only the day count carries a sign; the clock part is always
+/// added to it.
+pub struct Pandas;
+
+impl DurationDialect for Pandas {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let i = string.find(" day").ok_or_else(invalid)?;
+        let days: i64 = string[..i].parse().map_err(|_| invalid())?;
+        let s = &string[i + 4..];
+        let s = s.strip_prefix('s').unwrap_or(s);
+        let s = s.strip_prefix(' ').ok_or_else(invalid)?;
+        let s = s.strip_prefix('+').unwrap_or(s);
+        let t = clock(s).ok_or_else(invalid)?;
+        days_nanos(days, t).ok_or_else(|| Error::overflow(string))
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        let (days, t) = (d.div_euclid(DAY), d.rem_euclid(DAY));
+        let digits = if t % 1000 == 0 { 6 } else { 9 };
+        let sign = if days < 0 { "+" } else { "" };
+        Ok(format!(
+            "{} days {}{}",
+            days,
+            sign,
+            format_clock(t, true, digits)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pandas() -> Result<(), Error> {
+        assert_eq!(Pandas.parse("1 days 00:00:05")?, 86405000000000);
+        assert_eq!(Pandas.parse("0 days 00:00:00.001000")?, 1000000);
+        assert_eq!(Pandas.parse("0 days 00:00:00.000000001")?, 1);
+        assert_eq!(Pandas.parse("-1 days +23:59:59")?, -1000000000);
+        assert_eq!(Pandas.parse("10 days 01:02:03")?, 867723000000000);
+        assert!(Pandas.parse("00:00:05").is_err());
+        assert!(Pandas.parse("1 days").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pandas_format() -> Result<(), Error> {
+        assert_eq!(Pandas.format(86405000000000)?, "1 days 00:00:05");
+        assert_eq!(Pandas.format(1000000)?, "0 days 00:00:00.001000");
+        assert_eq!(Pandas.format(1)?, "0 days 00:00:00.000000001");
+        assert_eq!(Pandas.format(-1000000000)?, "-1 days +23:59:59");
+        for &d in &[0, 1, -1, 867723000000000, i64::MIN, i64::MAX] {
+            assert_eq!(Pandas.parse(&Pandas.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use super::{
+    fraction_nanos, number, offset_in, DurationDialect, DAY, HOUR, MICROSECOND, MILLISECOND,
+    MINUTE, SECOND,
+};
+use crate::{
+    component_nanos, format_duration, leading_fraction, leading_int, CalendarPolicy, Error,
+    UnitTable,
+};
+
+/// PostgresInterval parses and formats PostgreSQL interval text, such as
+/// "1 day 02:03:04", "3 hours 30 minutes", "-1 days +02:03:04" or
+/// "@ 2 mons ago", as `interval` values are written in SQL and printed by
+/// Postgres.
+///
+/// An interval is a run of signed numbers with a unit or a `[-]H:MM[:SS]`
+/// time, in any order. Units are case-insensitive and, as in Postgres,
+/// include their abbreviations, such as "mins" and "mon", from
+/// microseconds up to millennia. A number without a unit is in seconds,
+/// unless a time follows it, when it's days. A leading "@" is ignored and
+/// a trailing "ago" negates the interval.
+///
+/// A day is 24 hours. Months and years, and the decades and centuries made
+/// of them, have the lengths the policy gives them, or are rejected. When
+/// Postgres itself converts intervals to seconds, it counts a month as 30
+/// days, as [`CalendarPolicy::Fixed`] does, and a year as 365.25, as
+/// [`CalendarPolicy::Average`] does.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, PostgresInterval};
+/// use go_parse_duration::CalendarPolicy;
+///
+/// let interval = PostgresInterval::new(CalendarPolicy::Fixed);
+/// assert_eq!(interval.parse("1 day 02:03:04"), Ok(93784000000000));
+/// assert_eq!(interval.parse("3 hours 30 minutes"), Ok(12600000000000));
+/// assert_eq!(interval.parse("1 mon"), Ok(2592000000000000));
+/// assert_eq!(interval.format(93784000000000).unwrap(), "1 day 02:03:04");
+/// ```
+pub struct PostgresInterval {
+    policy: CalendarPolicy,
+}
+
+impl PostgresInterval {
+    /// new returns the dialect with months and years as long as the policy
+    /// says.
+    pub const fn new(policy: CalendarPolicy) -> PostgresInterval {
+        PostgresInterval { policy }
+    }
+
+    // calendar returns the length of the calendar unit "mo" or "y", or an
+    // error if the policy rejects it.
+    fn calendar(&self, unit: &str, name: &str, string: &str) -> Result<i64, Error> {
+        UnitTable::go()
+            .with_months_and_years(self.policy)
+            .get(unit)
+            .ok_or_else(|| {
+                Error::ParseError(format!(
+                    "unsupported interval field {} in duration {}",
+                    name, string
+                ))
+            })
+    }
+
+    // unit returns the length of the unit named word.
+    fn unit(&self, word: &str, string: &str) -> Result<i64, Error> {
+        let name = word.to_ascii_lowercase();
+        let (calendar, times) = match name.as_str() {
+            "microseconds" | "microsecond" | "usecs" | "usec" | "us" => return Ok(MICROSECOND),
+            "milliseconds" | "millisecond" | "msecs" | "msec" | "ms" => return Ok(MILLISECOND),
+            "seconds" | "second" | "secs" | "sec" | "s" => return Ok(SECOND),
+            "minutes" | "minute" | "mins" | "min" | "m" => return Ok(MINUTE),
+            "hours" | "hour" | "hrs" | "hr" | "h" => return Ok(HOUR),
+            "days" | "day" | "d" => return Ok(DAY),
+            "weeks" | "week" | "w" => return Ok(7 * DAY),
+            "months" | "month" | "mons" | "mon" => ("mo", 1),
+            "years" | "year" | "yrs" | "yr" | "y" => ("y", 1),
+            "decades" | "decade" => ("y", 10),
+            "centuries" | "century" => ("y", 100),
+            "millennia" | "millennium" => ("y", 1000),
+            _ => {
+                return Err(Error::unknown_unit_at(
+                    word,
+                    string,
+                    offset_in(string, word),
+                ))
+            }
+        };
+        self.calendar(calendar, &name, string)?
+            .checked_mul(times)
+            .ok_or_else(|| Error::overflow(string))
+    }
+}
+
+impl DurationDialect for PostgresInterval {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+        let overflow = || Error::overflow(string);
+        let ws = |c: char| c.is_ascii_whitespace();
+
+        let mut s = string.trim_matches(ws);
+        s = s.strip_prefix('@').unwrap_or(s).trim_start_matches(ws);
+        let ago = match s.rfind(ws) {
+            Some(i) if s[i + 1..].eq_ignore_ascii_case("ago") => {
+                s = s[..i].trim_end_matches(ws);
+                true
+            }
+            _ => false,
+        };
+        if s.is_empty() {
+            return Err(invalid());
+        }
+        let mut d: i64 = 0;
+        // bare is a number without a unit, which must come last, in seconds,
+        // or be a whole number of days followed by a time.
+        let mut bare: Option<(bool, i64, u64, f64)> = None;
+        while !s.is_empty() {
+            let (neg, rest) = match s.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, s.strip_prefix('+').unwrap_or(s)),
+            };
+            let pl = rest.len();
+            let (v, rest) = leading_int(rest).ok_or_else(overflow)?;
+            let pre = pl != rest.len();
+            let v = if let Some(rest) = rest.strip_prefix(':') {
+                let (t, rest) = time(v, rest).filter(|_| pre).ok_or_else(invalid)?;
+                match bare.take() {
+                    Some((neg, days, 0, _)) => {
+                        let days = days.checked_mul(DAY).ok_or_else(overflow)?;
+                        d = d
+                            .checked_add(if neg { -days } else { days })
+                            .ok_or_else(overflow)?;
+                    }
+                    Some(_) => return Err(invalid()),
+                    None => {}
+                }
+                s = rest;
+                t.ok_or_else(overflow)?
+            } else {
+                let (f, scale, rest) = match rest.strip_prefix('.') {
+                    Some(rest) => {
+                        let (f, scale, u) = leading_fraction(rest);
+                        if !pre && u.len() == rest.len() {
+                            return Err(invalid());
+                        }
+                        (f, scale, u)
+                    }
+                    None if pre => (0, 1f64, rest),
+                    None => return Err(invalid()),
+                };
+                let trimmed = rest.trim_start_matches(ws);
+                let i = trimmed
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(trimmed.len());
+                let (word, rest) = match trimmed.split_at(i) {
+                    ("", _) => ("", rest),
+                    split => split,
+                };
+                if !rest.is_empty() && !rest.starts_with(|c: char| ws(c) || c == '+' || c == '-') {
+                    return Err(invalid());
+                }
+                if bare.is_some() {
+                    return Err(invalid());
+                }
+                s = rest.trim_start_matches(ws);
+                if word.is_empty() {
+                    bare = Some((neg, v, f, scale));
+                    continue;
+                }
+                component_nanos(v, f, scale, self.unit(word, string)?).ok_or_else(overflow)?
+            };
+            d = d
+                .checked_add(if neg { -v } else { v })
+                .ok_or_else(overflow)?;
+            s = s.trim_start_matches(ws);
+        }
+        if let Some((neg, v, f, scale)) = bare {
+            let v = component_nanos(v, f, scale, SECOND).ok_or_else(overflow)?;
+            d = d
+                .checked_add(if neg { -v } else { v })
+                .ok_or_else(overflow)?;
+        }
+        Ok(if ago { -d } else { d })
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d % 1000 != 0 {
+            return Err(Error::ParseError(format!(
+                "unsupported format for duration {}",
+                format_duration(d)
+            )));
+        }
+        let sign = if d < 0 { "-" } else { "" };
+        let u = d.unsigned_abs();
+        let (day, hour, minute, second) = (DAY as u64, HOUR as u64, MINUTE as u64, SECOND as u64);
+        let (days, t) = (u / day, u % day);
+        let mut s = String::new();
+        if days != 0 {
+            let unit = if days == 1 && d > 0 { "day" } else { "days" };
+            s.push_str(&format!("{}{} {}", sign, days, unit));
+        }
+        if t != 0 || days == 0 {
+            if !s.is_empty() {
+                s.push(' ');
+            }
+            s.push_str(&format!(
+                "{}{:02}:{:02}:{:02}",
+                if t != 0 { sign } else { "" },
+                t / hour,
+                t / minute % 60,
+                t / second % 60
+            ));
+            let frac = format!("{:06}", t % second / 1000);
+            let frac = frac.trim_end_matches('0');
+            if !frac.is_empty() {
+                s.push('.');
+                s.push_str(frac);
+            }
+        }
+        Ok(s)
+    }
+}
+
+// time parses the MM[:SS[.f]] rest of an H:MM[:SS[.f]] time whose hours are
+// h, and returns it in nanoseconds, or None inside if it overflows, and the
+// rest of s.
+fn time(h: i64, s: &str) -> Option<(Option<i64>, &str)> {
+    let (m, s) = two_digits_below_60(s)?;
+    let (sec, ns, s) = match s.strip_prefix(':') {
+        Some(s) => {
+            let (sec, s) = two_digits_below_60(s)?;
+            match s.strip_prefix('.') {
+                Some(s) => {
+                    let (ns, s) = fraction_nanos(s)?;
+                    (sec, ns, s)
+                }
+                None => (sec, 0, s),
+            }
+        }
+        None => (0, 0, s),
+    };
+    if !s.is_empty() && !s.starts_with(|c: char| c.is_ascii_whitespace()) {
+        return None;
+    }
+    let t = h
+        .checked_mul(HOUR)
+        .and_then(|t| t.checked_add(m * MINUTE + sec * SECOND + ns));
+    Some((t, s))
+}
+
+// two_digits_below_60 consumes a two digit minutes or seconds field from s.
+fn two_digits_below_60(s: &str) -> Option<(i64, &str)> {
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if i != 2 {
+        return None;
+    }
+    match number(s)? {
+        (v, rest) if v < 60 => Some((v, rest)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_postgres_interval() -> Result<(), Error> {
+        let fixed = PostgresInterval::new(CalendarPolicy::Fixed);
+        assert_eq!(fixed.parse("1 day 02:03:04")?, 93784000000000);
+        assert_eq!(fixed.parse("3 hours 30 minutes")?, 12600000000000);
+        assert_eq!(fixed.parse("3 HOURS 30 Mins")?, 12600000000000);
+        assert_eq!(fixed.parse("-1 days +02:03:04")?, -79016000000000);
+        assert_eq!(fixed.parse("1 2:03:04")?, 93784000000000);
+        assert_eq!(fixed.parse("-1 -02:03:04")?, -93784000000000);
+        assert_eq!(fixed.parse("100:00:00.5")?, 360000500000000);
+        assert_eq!(fixed.parse("02:30")?, 9000000000000);
+        assert_eq!(fixed.parse("5")?, 5000000000);
+        assert_eq!(fixed.parse("1.5 hours")?, 5400000000000);
+        assert_eq!(fixed.parse("1day 12h")?, 129600000000000);
+        assert_eq!(fixed.parse("@ 1 hour 30 mins ago")?, -5400000000000);
+        assert_eq!(fixed.parse("2 weeks 3 us")?, 1209600000003000);
+        assert_eq!(fixed.parse("1 year 2 mons")?, 36720000000000000);
+        assert_eq!(fixed.parse("1 decade")?, 315360000000000000);
+        let average = PostgresInterval::new(CalendarPolicy::Average);
+        assert_eq!(average.parse("1 year")?, 31557600000000000);
+        let reject = PostgresInterval::new(CalendarPolicy::Reject);
+        assert_eq!(
+            reject.parse("1 Mon").unwrap_err(),
+            Error::ParseError(String::from(
+                "unsupported interval field mon in duration 1 Mon"
+            )),
+        );
+        assert_eq!(reject.parse("1 day")?, 86400000000000);
+        assert_eq!(
+            fixed.parse("1 fortnight").unwrap_err(),
+            Error::unknown_unit("fortnight", "1 fortnight"),
+        );
+        for s in [
+            "", "@", "ago", "1 2", "day", "1:60", "1:2", "1:00:60", "1 hour,", "--1 hour",
+            "1.5 2:00",
+        ] {
+            assert!(fixed.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(
+            fixed.parse("300 years").unwrap_err(),
+            Error::overflow("300 years")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_postgres_interval_format() -> Result<(), Error> {
+        let fixed = PostgresInterval::new(CalendarPolicy::Fixed);
+        assert_eq!(fixed.format(0)?, "00:00:00");
+        assert_eq!(fixed.format(93784000000000)?, "1 day 02:03:04");
+        assert_eq!(fixed.format(-93784500000000)?, "-1 days -02:03:04.5");
+        assert_eq!(fixed.format(172800000000000)?, "2 days");
+        assert_eq!(fixed.format(1000)?, "00:00:00.000001");
+        assert!(fixed.format(1).is_err());
+        for &d in &[1000, -1500000000, -86400000000000, i64::MAX / 1000 * 1000] {
+            assert_eq!(fixed.parse(&fixed.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use super::{number, offset_in, DurationDialect, DAY, HOUR, MILLISECOND, MINUTE, SECOND};
+use crate::{format_duration, Error};
+
+/// Prometheus parses and formats durations exactly as Prometheus validates
+/// them in PromQL and rule files, such as "5m", "1h30m" or "2w".
+///
+/// A Prometheus duration is a run of whole numbers with the units "y", "w",
+/// "d", "h", "m", "s" and "ms", each at most once and largest first, where
+/// a year is 365 days. There are no fractions, signs or whitespace, and
+/// "0" is the only number without a unit.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Prometheus};
+///
+/// assert_eq!(Prometheus.parse("1h30m"), Ok(5400000000000));
+/// assert!(Prometheus.parse("30m1h").is_err());
+/// assert_eq!(Prometheus.format(5400000000000).unwrap(), "1h30m");
+/// ```
+pub struct Prometheus;
+
+impl DurationDialect for Prometheus {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        if string == "0" {
+            return Ok(0);
+        }
+        if string.is_empty() {
+            return Err(invalid());
+        }
+        let mut s = string;
+        let mut d: i64 = 0;
+        // next is the index in UNITS of the largest unit that may come next.
+        let mut next = 0;
+        while !s.is_empty() {
+            let (v, rest) = number(s).ok_or_else(invalid)?;
+            let i = rest
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let u = &rest[..i];
+            s = &rest[i..];
+            let i = match UNITS.iter().position(|&(_, name)| name == u) {
+                Some(i) if i >= next => i,
+                Some(_) => return Err(invalid()),
+                None if u.is_empty() => return Err(Error::missing_unit(string)),
+                None => return Err(Error::unknown_unit_at(u, string, offset_in(string, u))),
+            };
+            next = i + 1;
+            d = v
+                .checked_mul(UNITS[i].0)
+                .and_then(|v| d.checked_add(v))
+                .ok_or_else(|| Error::overflow(string))?;
+        }
+        Ok(d)
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d < 0 || d % 1000000 != 0 {
+            return Err(Error::ParseError(format!(
+                "unsupported format for duration {}",
+                format_duration(d)
+            )));
+        }
+        if d == 0 {
+            return Ok(String::from("0s"));
+        }
+        let mut s = String::new();
+        let mut rest = d;
+        for &(unit, name) in &UNITS {
+            if rest >= unit {
+                s.push_str(&format!("{}{}", rest / unit, name));
+                rest %= unit;
+            }
+        }
+        Ok(s)
+    }
+}
+
+// UNITS lists the units of Prometheus durations in the order they must be
+// written.
+const UNITS: [(i64, &str); 7] = [
+    (365 * DAY, "y"),
+    (7 * DAY, "w"),
+    (DAY, "d"),
+    (HOUR, "h"),
+    (MINUTE, "m"),
+    (SECOND, "s"),
+    (MILLISECOND, "ms"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus() -> Result<(), Error> {
+        assert_eq!(Prometheus.parse("5m")?, 300000000000);
+        assert_eq!(Prometheus.parse("1h30m")?, 5400000000000);
+        assert_eq!(Prometheus.parse("1y2w3d")?, 33004800000000000);
+        assert_eq!(Prometheus.parse("1s500ms")?, 1500000000);
+        assert_eq!(Prometheus.parse("90m")?, 5400000000000);
+        assert_eq!(Prometheus.parse("0")?, 0);
+        assert_eq!(Prometheus.parse("0s")?, 0);
+        assert_eq!(Prometheus.parse("5").unwrap_err(), Error::missing_unit("5"));
+        assert_eq!(
+            Prometheus.parse("5us").unwrap_err(),
+            Error::unknown_unit("us", "5us"),
+        );
+        for s in [
+            "", "-5m", "+5m", "1.5h", "30m1h", "1m1m", "1h 30m", " 5m", "m",
+        ] {
+            assert!(Prometheus.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(
+            Prometheus.parse("300y").unwrap_err(),
+            Error::overflow("300y")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_prometheus_format() -> Result<(), Error> {
+        assert_eq!(Prometheus.format(0)?, "0s");
+        assert_eq!(Prometheus.format(5400000000000)?, "1h30m");
+        assert_eq!(Prometheus.format(33004800000000000)?, "1y2w3d");
+        assert_eq!(Prometheus.format(1500000000)?, "1s500ms");
+        assert!(Prometheus.format(1).is_err());
+        assert!(Prometheus.format(-1000000).is_err());
+        for &d in &[1000000, 86400000000000, 9223372036854000000] {
+            assert_eq!(Prometheus.parse(&Prometheus.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
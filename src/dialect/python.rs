@@ -0,0 +1,75 @@
+use super::{clock, days_nanos, format_clock, DurationDialect, DAY};
+use crate::Error;
+
+/// Python parses the output of Python's `str(timedelta)`, such as "2:03:04",
+/// "1 day, 2:03:04" or "-1 day, 23:59:59.500000".
+///
+/// As in Python, only the day count carries a sign; the clock part is always
+/// added to it. Formatting floors to whole microseconds, the resolution of
+/// `timedelta`, rounding negative durations toward negative infinity.
+pub struct Python;
+
+impl DurationDialect for Python {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let mut s = string;
+        let mut days: i64 = 0;
+        if let Some(i) = s.find(" day") {
+            days = s[..i].parse().map_err(|_| invalid())?;
+            let rest = &s[i + 4..];
+            let rest = rest.strip_prefix('s').unwrap_or(rest);
+            s = rest.strip_prefix(", ").ok_or_else(invalid)?;
+        }
+        let t = clock(s).ok_or_else(invalid)?;
+        days_nanos(days, t).ok_or_else(|| Error::overflow(string))
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        // DAY is a whole number of microseconds, so flooring the clock part
+        // floors the whole duration without overflowing at i64::MIN.
+        let (days, t) = (d.div_euclid(DAY), d.rem_euclid(DAY));
+        let t = t - t % 1000;
+        let clock = format_clock(t, false, 6);
+        Ok(match days {
+            0 => clock,
+            1 | -1 => format!("{} day, {}", days, clock),
+            _ => format!("{} days, {}", days, clock),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python() -> Result<(), Error> {
+        assert_eq!(Python.parse("0:00:00")?, 0);
+        assert_eq!(Python.parse("2:03:04")?, 7384000000000);
+        assert_eq!(Python.parse("1 day, 2:03:04")?, 93784000000000);
+        assert_eq!(Python.parse("2 days, 0:00:00.500000")?, 172800500000000);
+        assert_eq!(Python.parse("-1 day, 23:59:59")?, -1000000000);
+        assert_eq!(Python.parse("0:00:00.000001")?, 1000);
+        assert!(Python.parse("1 day 2:03:04").is_err());
+        assert!(Python.parse("2:3:04").is_err());
+        assert!(Python.parse("2:03:60").is_err());
+        assert!(Python.parse("999999999 days, 0:00:00").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_python_format() -> Result<(), Error> {
+        assert_eq!(Python.format(0)?, "0:00:00");
+        assert_eq!(Python.format(93784000000000)?, "1 day, 2:03:04");
+        assert_eq!(Python.format(172800500000000)?, "2 days, 0:00:00.500000");
+        assert_eq!(Python.format(-1000000000)?, "-1 day, 23:59:59");
+        assert_eq!(Python.format(1999)?, "0:00:00.000001");
+        assert_eq!(Python.format(-1)?, "-1 day, 23:59:59.999999");
+        assert_eq!(Python.format(i64::MIN)?, "-106752 days, 0:12:43.145224");
+        for &d in &[0, 1000, -1000, 93784000000000, -172800500000000] {
+            assert_eq!(Python.parse(&Python.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use super::{offset_in, DurationDialect, DAY, HOUR, MINUTE, SECOND};
+use crate::{component_nanos, leading_fraction, leading_int, Error};
+
+/// Rails parses ActiveSupport-style duration literals such as "2.hours",
+/// "30.minutes" or "1.5.days".
+///
+/// Terms may be joined with `+` and `-`, e.g. "1.hour + 30.minutes". Months
+/// and years use ActiveSupport's lengths: 1/12 of a Gregorian year
+/// (2629746 seconds) and a Gregorian year (31556952 seconds).
+pub struct Rails;
+
+impl DurationDialect for Rails {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let mut s = string.trim();
+        if s.is_empty() {
+            return Err(Error::invalid_syntax(string));
+        }
+        let mut d: i64 = 0;
+        let mut neg = false;
+        loop {
+            let (v, rest) = term(s, string)?;
+            let v = if neg { v.checked_neg() } else { Some(v) };
+            match v.and_then(|v| d.checked_add(v)) {
+                Some(_d) => d = _d,
+                None => {
+                    return Err(Error::overflow(string));
+                }
+            }
+            s = rest.trim_start();
+            if s.is_empty() {
+                return Ok(d);
+            }
+            // Consume the operator joining this term to the next one.
+            neg = match s.as_bytes()[0] {
+                b'+' => false,
+                b'-' => true,
+                _ => {
+                    return Err(Error::invalid_syntax(string));
+                }
+            };
+            s = s[1..].trim_start();
+        }
+    }
+}
+
+// term consumes a single -?[0-9]+(\.[0-9]+)?\.[a-z]+ term from s and returns
+// its value in nanoseconds along with the remainder.
+fn term<'a>(s: &'a str, string: &str) -> Result<(i64, &'a str), Error> {
+    let invalid = || Error::invalid_syntax(string);
+
+    let (neg, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let pl = s.len();
+    let (v, s) = leading_int(s).ok_or_else(invalid)?;
+    if pl == s.len() {
+        return Err(invalid());
+    }
+    let s = s.strip_prefix('.').ok_or_else(invalid)?;
+    let (f, scale, s) = if s.starts_with(|c: char| c.is_ascii_digit()) {
+        let (f, scale, s) = leading_fraction(s);
+        (f, scale, s.strip_prefix('.').ok_or_else(invalid)?)
+    } else {
+        (0, 1f64, s)
+    };
+
+    let i = s
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(s.len());
+    if i == 0 {
+        return Err(Error::missing_unit(string));
+    }
+    let u = &s[..i];
+    let unit = match u {
+        "second" | "seconds" => SECOND,
+        "minute" | "minutes" => MINUTE,
+        "hour" | "hours" => HOUR,
+        "day" | "days" => DAY,
+        "week" | "weeks" => 7 * DAY,
+        "fortnight" | "fortnights" => 14 * DAY,
+        "month" | "months" => 2629746 * SECOND,
+        "year" | "years" => 31556952 * SECOND,
+        _ => {
+            return Err(Error::unknown_unit_at(u, string, offset_in(string, u)));
+        }
+    };
+    let v = component_nanos(v, f, scale, unit).ok_or_else(|| Error::overflow(string))?;
+    Ok((if neg { -v } else { v }, &s[i..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rails() -> Result<(), Error> {
+        assert_eq!(Rails.parse("2.hours")?, 7200000000000);
+        assert_eq!(Rails.parse("30.minutes")?, 1800000000000);
+        assert_eq!(Rails.parse("1.day")?, 86400000000000);
+        assert_eq!(Rails.parse("1.5.hours")?, 5400000000000);
+        assert_eq!(Rails.parse("1.hour + 30.minutes")?, 5400000000000);
+        assert_eq!(Rails.parse("1.hour-30.minutes")?, 1800000000000);
+        assert_eq!(Rails.parse("-5.seconds")?, -5000000000);
+        assert_eq!(Rails.parse("1.month")?, 2629746000000000);
+        assert_eq!(
+            Rails.parse("2.parsecs").unwrap_err(),
+            Error::unknown_unit("parsecs", "2.parsecs"),
+        );
+        assert!(Rails.parse("2 hours").is_err());
+        assert!(Rails.parse("hours").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use super::{DurationDialect, DAY, HOUR, MINUTE, SECOND};
+use crate::Error;
+
+/// Sleep parses durations as GNU coreutils `sleep` reads its arguments,
+/// such as "1.5", "30s", "5m" or "1h 30m", for command line tools that take
+/// the same values.
+///
+/// Each whitespace-separated argument is a non-negative floating point
+/// number, such as "1.5", ".5" or "1e3", with an optional suffix "s", "m",
+/// "h" or "d", and without one it's in seconds. The arguments are added up,
+/// as `sleep 1m 30s` does. "inf" and "infinity" parse to `i64::MAX`.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Sleep};
+///
+/// assert_eq!(Sleep.parse("1.5"), Ok(1500000000));
+/// assert_eq!(Sleep.parse("1m 30s"), Ok(90000000000));
+/// ```
+pub struct Sleep;
+
+impl DurationDialect for Sleep {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+        let overflow = || Error::overflow(string);
+
+        // d is None once the finite arguments overflow, which only matters
+        // if none of them is infinite.
+        let mut d = Some(0i64);
+        let mut infinite = false;
+        let mut any = false;
+        for arg in string.split_ascii_whitespace() {
+            any = true;
+            let (v, unit) = match arg.as_bytes()[arg.len() - 1] {
+                b's' => (&arg[..arg.len() - 1], SECOND),
+                b'm' => (&arg[..arg.len() - 1], MINUTE),
+                b'h' => (&arg[..arg.len() - 1], HOUR),
+                b'd' => (&arg[..arg.len() - 1], DAY),
+                _ => (arg, SECOND),
+            };
+            // str::parse also accepts "nan", which sleep rejects, and "inf",
+            // which it sleeps on forever.
+            let v: f64 = v.parse().map_err(|_| invalid())?;
+            if v.is_nan() || v < 0.0 {
+                return Err(invalid());
+            }
+            if v.is_infinite() {
+                infinite = true;
+                continue;
+            }
+            let ns = v * unit as f64;
+            d = d
+                .filter(|_| ns < i64::MAX as f64)
+                .and_then(|d| d.checked_add(ns as i64));
+        }
+        if !any {
+            return Err(invalid());
+        }
+        if infinite {
+            return Ok(i64::MAX);
+        }
+        d.ok_or_else(overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep() -> Result<(), Error> {
+        assert_eq!(Sleep.parse("1.5")?, 1500000000);
+        assert_eq!(Sleep.parse("30s")?, 30000000000);
+        assert_eq!(Sleep.parse("5m")?, 300000000000);
+        assert_eq!(Sleep.parse("0.5h")?, 1800000000000);
+        assert_eq!(Sleep.parse("2d")?, 172800000000000);
+        assert_eq!(Sleep.parse(" 1m  30 ")?, 90000000000);
+        assert_eq!(Sleep.parse(".5")?, 500000000);
+        assert_eq!(Sleep.parse("1e3")?, 1000000000000);
+        assert_eq!(Sleep.parse("+2.")?, 2000000000);
+        assert_eq!(Sleep.parse("0")?, 0);
+        assert_eq!(Sleep.parse("infinity")?, i64::MAX);
+        assert_eq!(Sleep.parse("1s inf")?, i64::MAX);
+        assert_eq!(Sleep.parse("1e300 inf")?, i64::MAX);
+        assert!(Sleep.parse("inf junk").is_err());
+        assert!(Sleep.parse("inf -1").is_err());
+        for s in ["", " ", "-1", "nan", "5ms", "1h30m", "1w", "s", "1 s"] {
+            assert!(Sleep.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(Sleep.parse("1e300").unwrap_err(), Error::overflow("1e300"));
+        assert!(Sleep.parse("106751d 106751d").is_err());
+        Ok(())
+    }
+}
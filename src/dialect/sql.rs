@@ -0,0 +1,140 @@
+use super::{fraction_nanos, number, DurationDialect, DAY, HOUR, MINUTE, SECOND};
+use crate::Error;
+
+/// SqlInterval parses ANSI SQL day-time interval literals such as
+/// `INTERVAL '1' HOUR`, `INTERVAL '02:30' HOUR TO MINUTE` or
+/// `INTERVAL '-1 02:03:04.5' DAY TO SECOND`.
+///
+/// Keywords are case-insensitive and field precisions such as `HOUR(3)` are
+/// accepted and ignored. Year-month intervals have no fixed length and are
+/// rejected.
+pub struct SqlInterval;
+
+// Day-time interval fields, from the most to the least significant one, and
+// the upper bound of each field when it isn't the leading one.
+const FIELDS: [(&str, i64, i64); 4] = [
+    ("DAY", DAY, i64::MAX),
+    ("HOUR", HOUR, 24),
+    ("MINUTE", MINUTE, 60),
+    ("SECOND", SECOND, 60),
+];
+
+impl DurationDialect for SqlInterval {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let s = string.trim();
+        let s = match s.get(..8) {
+            Some(keyword) if keyword.eq_ignore_ascii_case("INTERVAL") => &s[8..],
+            _ => return Err(invalid()),
+        };
+        let s = s.trim_start().strip_prefix('\'').ok_or_else(invalid)?;
+        let i = s.find('\'').ok_or_else(invalid)?;
+        let value = &s[..i];
+
+        let mut words = s[i + 1..].split_whitespace();
+        let start = field(words.next().ok_or_else(invalid)?, string)?;
+        let end = match words.next() {
+            Some(to) if to.eq_ignore_ascii_case("TO") => {
+                field(words.next().ok_or_else(invalid)?, string)?
+            }
+            Some(_) => return Err(invalid()),
+            None => start,
+        };
+        if end < start || words.next().is_some() {
+            return Err(invalid());
+        }
+
+        value_nanos(value, start, end).ok_or_else(invalid)
+    }
+}
+
+// field resolves an interval field name, ignoring any precision, to its
+// index in FIELDS.
+fn field(word: &str, string: &str) -> Result<usize, Error> {
+    let name = match word.find('(') {
+        Some(i) if word.ends_with(')') => &word[..i],
+        Some(_) => return Err(Error::invalid_syntax(string)),
+        None => word,
+    };
+    FIELDS
+        .iter()
+        .position(|(n, _, _)| n.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            Error::ParseError(format!(
+                "unsupported interval field {} in duration {}",
+                name, string
+            ))
+        })
+}
+
+// value_nanos parses the quoted part of an interval literal whose fields run
+// from FIELDS[start] to FIELDS[end].
+fn value_nanos(s: &str, start: usize, end: usize) -> Option<i64> {
+    let (neg, mut s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let mut d: i64 = 0;
+    for (i, &(_, unit, limit)) in FIELDS.iter().enumerate().take(end + 1).skip(start) {
+        if i > start {
+            let sep = if i == 1 { ' ' } else { ':' };
+            s = s.strip_prefix(sep)?;
+        }
+        let pl = s.len();
+        let (v, rest) = number(s)?;
+        if i > start && (pl - rest.len() > 2 || v >= limit) {
+            return None;
+        }
+        s = rest;
+        d = d.checked_add(v.checked_mul(unit)?)?;
+    }
+    if end == 3 {
+        if let Some(rest) = s.strip_prefix('.') {
+            let (ns, rest) = fraction_nanos(rest)?;
+            s = rest;
+            d = d.checked_add(ns)?;
+        }
+    }
+    if !s.is_empty() {
+        return None;
+    }
+    Some(if neg { -d } else { d })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sql_interval() -> Result<(), Error> {
+        assert_eq!(SqlInterval.parse("INTERVAL '1' HOUR")?, 3600000000000);
+        assert_eq!(
+            SqlInterval.parse("INTERVAL '02:30' HOUR TO MINUTE")?,
+            9000000000000
+        );
+        assert_eq!(
+            SqlInterval.parse("interval '1 02:03:04.5' day to second")?,
+            93784500000000
+        );
+        assert_eq!(SqlInterval.parse("INTERVAL '-3' DAY")?, -259200000000000);
+        assert_eq!(SqlInterval.parse("INTERVAL '1.25' SECOND")?, 1250000000);
+        assert_eq!(
+            SqlInterval.parse("INTERVAL '100' HOUR(3)")?,
+            360000000000000
+        );
+        assert_eq!(
+            SqlInterval.parse("INTERVAL '1' YEAR").unwrap_err(),
+            Error::ParseError(String::from(
+                "unsupported interval field YEAR in duration INTERVAL '1' YEAR"
+            )),
+        );
+        assert!(SqlInterval
+            .parse("INTERVAL '02:60' HOUR TO MINUTE")
+            .is_err());
+        assert!(SqlInterval.parse("INTERVAL '1.5' HOUR").is_err());
+        assert!(SqlInterval.parse("INTERVAL '1' MINUTE TO HOUR").is_err());
+        assert!(SqlInterval.parse("INTERVA\u{e9} 1 day").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use super::{
+    fraction_nanos, number, offset_in, two_digits, DurationDialect, DAY, HOUR, MINUTE, SECOND,
+};
+use crate::{component_nanos, leading_fraction, leading_int, Error};
+
+/// Sqlite parses the time-delta modifiers of SQLite's date and time
+/// functions, such as "+1 hour", "-30 minutes", "+1.5 seconds" or
+/// "-01:30:00.500".
+///
+/// Unit names are case-insensitive and may be singular or plural. The
+/// "months" and "years" modifiers depend on the date they're applied to and
+/// are rejected.
+pub struct Sqlite;
+
+impl DurationDialect for Sqlite {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let s = string.trim();
+        let (neg, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let d = if s.contains(':') {
+            offset(s).ok_or_else(invalid)?
+        } else {
+            amount(s, string)?
+        };
+        Ok(if neg { -d } else { d })
+    }
+}
+
+// offset parses the HH:MM(:SS(.SSS)?)? form of a modifier.
+fn offset(s: &str) -> Option<i64> {
+    let (h, s) = number(s)?;
+    let s = s.strip_prefix(':')?;
+    let (m, mut s) = two_digits(s)?;
+    let mut d = h.checked_mul(HOUR)?.checked_add(m * MINUTE)?;
+    if let Some(rest) = s.strip_prefix(':') {
+        let (sec, rest) = two_digits(rest)?;
+        s = rest;
+        d = d.checked_add(sec * SECOND)?;
+        if let Some(rest) = s.strip_prefix('.') {
+            let (ns, rest) = fraction_nanos(rest)?;
+            s = rest;
+            d = d.checked_add(ns)?;
+        }
+    }
+    if m >= 60 || !s.is_empty() {
+        return None;
+    }
+    Some(d)
+}
+
+// amount parses the "NNN.NNN units" form of a modifier.
+fn amount(s: &str, string: &str) -> Result<i64, Error> {
+    let invalid = || Error::invalid_syntax(string);
+
+    let pl = s.len();
+    let (v, s) = leading_int(s).ok_or_else(invalid)?;
+    let pre = pl != s.len();
+    let (f, scale, s) = match s.strip_prefix('.') {
+        Some(s) => leading_fraction(s),
+        None => (0, 1f64, s),
+    };
+    if !pre && f == 0 && scale == 1f64 {
+        return Err(invalid());
+    }
+    let u = s.trim_start();
+    if u.len() == s.len() || u.is_empty() {
+        return Err(invalid());
+    }
+    let at = offset_in(string, u);
+    let u = u.to_ascii_lowercase();
+    let unit = match u.strip_suffix('s').unwrap_or(&u) {
+        "day" => DAY,
+        "hour" => HOUR,
+        "minute" => MINUTE,
+        "second" => SECOND,
+        _ => {
+            return Err(Error::unknown_unit_at(&u, string, at));
+        }
+    };
+    component_nanos(v, f, scale, unit).ok_or_else(|| Error::overflow(string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite() -> Result<(), Error> {
+        assert_eq!(Sqlite.parse("+1 hour")?, 3600000000000);
+        assert_eq!(Sqlite.parse("-30 minutes")?, -1800000000000);
+        assert_eq!(Sqlite.parse("+1.5 seconds")?, 1500000000);
+        assert_eq!(Sqlite.parse("2 Days")?, 172800000000000);
+        assert_eq!(Sqlite.parse("+.5 day")?, 43200000000000);
+        assert_eq!(Sqlite.parse("-01:30")?, -5400000000000);
+        assert_eq!(Sqlite.parse("+00:00:01.250")?, 1250000000);
+        assert_eq!(
+            Sqlite.parse("+1 months").unwrap_err(),
+            Error::unknown_unit("months", "+1 months"),
+        );
+        assert!(Sqlite.parse("+1hour").is_err());
+        assert!(Sqlite.parse("+ hours").is_err());
+        Ok(())
+    }
+}
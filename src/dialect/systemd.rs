@@ -0,0 +1,172 @@
+use super::{offset_in, DurationDialect, DAY, HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{component_nanos, format_duration, leading_fraction, leading_int, Error};
+
+/// Systemd parses and formats time spans as systemd.time(7) describes them,
+/// such as "2h 30min", "1.5days" or "5s 500ms", for reading unit files and
+/// systemd-style configs.
+///
+/// A time span is a run of numbers, each with an optional fraction and unit
+/// and optional whitespace between them. A number without a unit is in
+/// seconds, a month is 30.44 days and a year 365.25 days. "infinity" parses
+/// to `i64::MAX`, which also formats as "infinity". Spans can't be negative.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, Systemd};
+///
+/// assert_eq!(Systemd.parse("2h 30min"), Ok(9000000000000));
+/// assert_eq!(Systemd.parse("1.5days"), Ok(129600000000000));
+/// assert_eq!(Systemd.parse("infinity"), Ok(i64::MAX));
+/// assert_eq!(Systemd.format(9000000000000).unwrap(), "2h 30min");
+/// ```
+pub struct Systemd;
+
+impl DurationDialect for Systemd {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let mut s = string.trim_matches(|c: char| c.is_ascii_whitespace());
+        if s == "infinity" {
+            return Ok(i64::MAX);
+        }
+        if s.is_empty() {
+            return Err(invalid());
+        }
+        let mut d: i64 = 0;
+        while !s.is_empty() {
+            let pl = s.len();
+            let (v, rest) = leading_int(s).ok_or_else(|| Error::overflow(string))?;
+            let pre = pl != rest.len();
+            let (f, scale, rest) = match rest.strip_prefix('.') {
+                Some(rest) => {
+                    let (f, scale, u) = leading_fraction(rest);
+                    if !pre && u.len() == rest.len() {
+                        return Err(invalid());
+                    }
+                    (f, scale, u)
+                }
+                None if pre => (0, 1f64, rest),
+                None => return Err(invalid()),
+            };
+            let spaced = rest.starts_with(|c: char| c.is_ascii_whitespace());
+            let rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            let i = rest
+                .find(|c: char| c.is_ascii_whitespace() || c.is_ascii_digit() || c == '.')
+                .unwrap_or(rest.len());
+            let u = &rest[..i];
+            if u.is_empty() && !spaced && !rest.is_empty() {
+                // a number runs straight into another, as in "1.2.3s"
+                return Err(invalid());
+            }
+            s = rest[i..].trim_start_matches(|c: char| c.is_ascii_whitespace());
+            let unit = match u {
+                "" => SECOND,
+                "nsec" | "ns" => 1,
+                "usec" | "us" | "µs" | "μs" => MICROSECOND,
+                "msec" | "ms" => MILLISECOND,
+                "seconds" | "second" | "sec" | "s" => SECOND,
+                "minutes" | "minute" | "min" | "m" => MINUTE,
+                "hours" | "hour" | "hr" | "h" => HOUR,
+                "days" | "day" | "d" => DAY,
+                "weeks" | "week" | "w" => 7 * DAY,
+                "months" | "month" | "M" => MONTH,
+                "years" | "year" | "y" => YEAR,
+                _ => {
+                    return Err(Error::unknown_unit_at(u, string, offset_in(string, u)));
+                }
+            };
+            d = component_nanos(v, f, scale, unit)
+                .and_then(|v| d.checked_add(v))
+                .ok_or_else(|| Error::overflow(string))?;
+        }
+        Ok(d)
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d < 0 {
+            return Err(Error::ParseError(format!(
+                "negative duration {} in systemd format",
+                format_duration(d)
+            )));
+        }
+        if d == i64::MAX {
+            return Ok(String::from("infinity"));
+        }
+        if d == 0 {
+            return Ok(String::from("0"));
+        }
+        let mut parts = Vec::new();
+        let mut rest = d;
+        for &(unit, name) in &UNITS {
+            if rest >= unit {
+                parts.push(format!("{}{}", rest / unit, name));
+                rest %= unit;
+            }
+        }
+        Ok(parts.join(" "))
+    }
+}
+
+// MONTH and YEAR are systemd's average month and year, 30.44 and 365.25
+// days.
+const MONTH: i64 = 2629800 * SECOND;
+const YEAR: i64 = 31557600 * SECOND;
+
+// UNITS lists the units Systemd formats with, largest first.
+const UNITS: [(i64, &str); 10] = [
+    (YEAR, "y"),
+    (MONTH, "month"),
+    (7 * DAY, "w"),
+    (DAY, "d"),
+    (HOUR, "h"),
+    (MINUTE, "min"),
+    (SECOND, "s"),
+    (MILLISECOND, "ms"),
+    (MICROSECOND, "us"),
+    (1, "ns"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systemd() -> Result<(), Error> {
+        assert_eq!(Systemd.parse("2h 30min")?, 9000000000000);
+        assert_eq!(Systemd.parse("1.5days")?, 129600000000000);
+        assert_eq!(Systemd.parse("2h30min")?, 9000000000000);
+        assert_eq!(Systemd.parse(" 5 s 500 msec ")?, 5500000000);
+        assert_eq!(Systemd.parse("300")?, 300000000000);
+        assert_eq!(Systemd.parse("1min 30")?, 90000000000);
+        assert_eq!(Systemd.parse(".5s")?, 500000000);
+        assert_eq!(Systemd.parse("20usec 3µs 4ns")?, 23004);
+        assert_eq!(Systemd.parse("1y 1M")?, 34187400000000000);
+        assert_eq!(Systemd.parse("infinity")?, i64::MAX);
+        assert_eq!(
+            Systemd.parse("5mn").unwrap_err(),
+            Error::unknown_unit("mn", "5mn"),
+        );
+        assert_eq!(
+            Systemd.parse(" 5m 5mn ").unwrap_err(),
+            Error::unknown_unit_at("mn", " 5m 5mn ", 5),
+        );
+        for s in ["", "-5s", "+5s", "s", "1.2.3s", "infinitys", "1s infinity"] {
+            assert!(Systemd.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(Systemd.parse("300y").unwrap_err(), Error::overflow("300y"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_systemd_format() -> Result<(), Error> {
+        assert_eq!(Systemd.format(0)?, "0");
+        assert_eq!(Systemd.format(9000000000000)?, "2h 30min");
+        assert_eq!(Systemd.format(129600000000000)?, "1d 12h");
+        assert_eq!(Systemd.format(5500000000)?, "5s 500ms");
+        assert_eq!(Systemd.format(i64::MAX)?, "infinity");
+        assert!(Systemd.format(-1).is_err());
+        for &d in &[1, 1500001, 34187400000000000, i64::MAX - 1] {
+            assert_eq!(Systemd.parse(&Systemd.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
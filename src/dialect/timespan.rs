@@ -0,0 +1,155 @@
+use super::{days_nanos, fraction_nanos, number, DurationDialect, DAY, HOUR, MINUTE, SECOND};
+use crate::{format_duration, Error};
+
+/// TimeSpan parses and formats .NET `TimeSpan` values, such as
+/// "1.02:03:04.5000000", "02:30" or "-00:00:01", for services that talk to
+/// C# backends.
+///
+/// It parses what `TimeSpan.Parse` does with the invariant culture:
+/// `[ws][-]{d | [d.]hh:mm[:ss[.fffffff]]}[ws]`, where the hours are below
+/// 24, the minutes and seconds below 60 and each one or two digits, and
+/// the fraction up to seven digits, as ticks are 100ns. It formats with the
+/// constant ("c") format, `[-][d.]hh:mm:ss[.fffffff]`, which only takes
+/// whole ticks.
+///
+/// ```rust
+/// use go_parse_duration::dialect::{DurationDialect, TimeSpan};
+///
+/// assert_eq!(TimeSpan.parse("1.02:03:04.5000000"), Ok(93784500000000));
+/// assert_eq!(TimeSpan.format(93784500000000).unwrap(), "1.02:03:04.5000000");
+/// ```
+pub struct TimeSpan;
+
+impl DurationDialect for TimeSpan {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+        let overflow = || Error::overflow(string);
+
+        let s = string.trim();
+        let (neg, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (v, rest) = number(s).ok_or_else(invalid)?;
+        let (days, s) = match rest.strip_prefix('.') {
+            // A "." before the first colon separates the days.
+            Some(rest) if rest.find(':') > Some(0) => (v, rest),
+            _ if rest.is_empty() => {
+                let d = days_nanos(v, 0).ok_or_else(overflow)?;
+                return Ok(if neg { -d } else { d });
+            }
+            _ => (0, s),
+        };
+        let (h, s) = field(s, 24).ok_or_else(invalid)?;
+        let s = s.strip_prefix(':').ok_or_else(invalid)?;
+        let (m, s) = field(s, 60).ok_or_else(invalid)?;
+        let (sec, ns) = match s.strip_prefix(':') {
+            Some(s) => {
+                let (sec, s) = field(s, 60).ok_or_else(invalid)?;
+                match s.strip_prefix('.') {
+                    Some(s) if s.len() <= 7 => match fraction_nanos(s) {
+                        Some((ns, "")) => (sec, ns),
+                        _ => return Err(invalid()),
+                    },
+                    None if s.is_empty() => (sec, 0),
+                    _ => return Err(invalid()),
+                }
+            }
+            None if s.is_empty() => (0, 0),
+            None => return Err(invalid()),
+        };
+        let d = days_nanos(days, h * HOUR + m * MINUTE + sec * SECOND + ns).ok_or_else(overflow)?;
+        Ok(if neg { -d } else { d })
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d % 100 != 0 {
+            return Err(Error::ParseError(format!(
+                "unsupported format for duration {}",
+                format_duration(d)
+            )));
+        }
+        let sign = if d < 0 { "-" } else { "" };
+        let u = d.unsigned_abs();
+        let (day, hour, minute, second) = (DAY as u64, HOUR as u64, MINUTE as u64, SECOND as u64);
+        let mut s = String::from(sign);
+        if u >= day {
+            s.push_str(&format!("{}.", u / day));
+        }
+        s.push_str(&format!(
+            "{:02}:{:02}:{:02}",
+            u / hour % 24,
+            u / minute % 60,
+            u / second % 60
+        ));
+        let frac = u % second;
+        if frac != 0 {
+            s.push_str(&format!(".{:07}", frac / 100));
+        }
+        Ok(s)
+    }
+}
+
+// field consumes a one or two digit field from s, which must be below max.
+fn field(s: &str, max: i64) -> Option<(i64, &str)> {
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if i == 0 || i > 2 {
+        return None;
+    }
+    let (v, _) = number(&s[..i])?;
+    if v >= max {
+        return None;
+    }
+    Some((v, &s[i..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timespan() -> Result<(), Error> {
+        assert_eq!(TimeSpan.parse("1.02:03:04.5000000")?, 93784500000000);
+        assert_eq!(TimeSpan.parse("02:03:04")?, 7384000000000);
+        assert_eq!(TimeSpan.parse("2:30")?, 9000000000000);
+        assert_eq!(TimeSpan.parse("1.2:30")?, 95400000000000);
+        assert_eq!(TimeSpan.parse("3")?, 259200000000000);
+        assert_eq!(TimeSpan.parse("-00:00:01")?, -1000000000);
+        assert_eq!(TimeSpan.parse(" 00:00:00.0000001 ")?, 100);
+        assert_eq!(TimeSpan.parse("00:00:00.5")?, 500000000);
+        for s in [
+            "",
+            "-",
+            "24:00",
+            "00:60",
+            "00:00:60",
+            "1.",
+            "1.02",
+            "00:00:00.00000001",
+            "00:00.5",
+            "1:2:3:4",
+            "+00:01",
+            "123:00",
+        ] {
+            assert!(TimeSpan.parse(s).is_err(), "{}", s);
+        }
+        assert_eq!(
+            TimeSpan.parse("10675199.02:48:05.4775807").unwrap_err(),
+            Error::overflow("10675199.02:48:05.4775807")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_timespan_format() -> Result<(), Error> {
+        assert_eq!(TimeSpan.format(0)?, "00:00:00");
+        assert_eq!(TimeSpan.format(93784500000000)?, "1.02:03:04.5000000");
+        assert_eq!(TimeSpan.format(-1000000000)?, "-00:00:01");
+        assert_eq!(TimeSpan.format(100)?, "00:00:00.0000001");
+        assert!(TimeSpan.format(1).is_err());
+        for &d in &[100, -93784500000000, i64::MAX / 100 * 100] {
+            assert_eq!(TimeSpan.parse(&TimeSpan.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
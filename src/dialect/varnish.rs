@@ -0,0 +1,122 @@
+use super::{offset_in, signed, DurationDialect, DAY, HOUR, MILLISECOND, MINUTE, SECOND};
+use crate::{component_magnitude, leading_fraction, leading_int, Error};
+
+/// Varnish parses Varnish VCL durations, such as "1.5s", "10m" or "-2w".
+///
+/// A VCL duration is a single number with an optional fraction followed by
+/// one of the units "ms", "s", "m", "h", "d", "w" or "y", where a year is
+/// 365 days. Unlike Go, the unit is required even for zero.
+pub struct Varnish;
+
+impl DurationDialect for Varnish {
+    fn parse(&self, string: &str) -> Result<i64, Error> {
+        let invalid = || Error::invalid_syntax(string);
+
+        let (neg, s) = match string.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let pl = s.len();
+        let (v, s) = leading_int(s).ok_or_else(invalid)?;
+        if pl == s.len() {
+            return Err(invalid());
+        }
+        let (f, scale, u) = match s.strip_prefix('.') {
+            Some(s) => {
+                let (f, scale, u) = leading_fraction(s);
+                if u.len() == s.len() {
+                    return Err(invalid());
+                }
+                (f, scale, u)
+            }
+            None => (0, 1f64, s),
+        };
+        let unit = match u {
+            "ms" => MILLISECOND,
+            "s" => SECOND,
+            "m" => MINUTE,
+            "h" => HOUR,
+            "d" => DAY,
+            "w" => 7 * DAY,
+            "y" => 365 * DAY,
+            "" => {
+                return Err(Error::missing_unit(string));
+            }
+            _ => {
+                return Err(Error::unknown_unit_at(u, string, offset_in(string, u)));
+            }
+        };
+        component_magnitude(v as u64, f, scale, unit, false)
+            .and_then(|d| signed(neg, d))
+            .ok_or_else(|| Error::overflow(string))
+    }
+
+    fn format(&self, d: i64) -> Result<String, Error> {
+        if d == 0 {
+            return Ok(String::from("0s"));
+        }
+        let name = UNITS.iter().find(|&&(unit, _)| d % unit == 0);
+        if let Some(&(unit, name)) = name {
+            return Ok(format!("{}{}", d / unit, name));
+        }
+        let sign = if d < 0 { "-" } else { "" };
+        let u = d.unsigned_abs();
+        let frac = format!("{:09}", u % SECOND as u64);
+        Ok(format!(
+            "{}{}.{}s",
+            sign,
+            u / SECOND as u64,
+            frac.trim_end_matches('0')
+        ))
+    }
+}
+
+// UNITS lists the units Varnish formats whole durations with, largest
+// first.
+const UNITS: [(i64, &str); 7] = [
+    (365 * DAY, "y"),
+    (7 * DAY, "w"),
+    (DAY, "d"),
+    (HOUR, "h"),
+    (MINUTE, "m"),
+    (SECOND, "s"),
+    (MILLISECOND, "ms"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varnish() -> Result<(), Error> {
+        assert_eq!(Varnish.parse("1.5s")?, 1500000000);
+        assert_eq!(Varnish.parse("250ms")?, 250000000);
+        assert_eq!(Varnish.parse("10m")?, 600000000000);
+        assert_eq!(Varnish.parse("-2w")?, -1209600000000000);
+        assert_eq!(Varnish.parse("1y")?, 31536000000000000);
+        assert_eq!(Varnish.parse("0s")?, 0);
+        assert_eq!(Varnish.parse("0").unwrap_err(), Error::missing_unit("0"),);
+        assert_eq!(
+            Varnish.parse("5us").unwrap_err(),
+            Error::unknown_unit("us", "5us"),
+        );
+        assert!(Varnish.parse("1h30m").is_err());
+        assert!(Varnish.parse(".5s").is_err());
+        assert!(Varnish.parse("1.s").is_err());
+        assert!(Varnish.parse("300y").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_varnish_format() -> Result<(), Error> {
+        assert_eq!(Varnish.format(1500000000)?, "1500ms");
+        assert_eq!(Varnish.format(-1209600000000000)?, "-2w");
+        assert_eq!(Varnish.format(5400000000000)?, "90m");
+        assert_eq!(Varnish.format(1000001)?, "0.001000001s");
+        assert_eq!(Varnish.format(0)?, "0s");
+        for &d in &[1, -1500000001, 31536000000000000, i64::MIN] {
+            assert_eq!(Varnish.parse(&Varnish.format(d)?)?, d);
+        }
+        Ok(())
+    }
+}
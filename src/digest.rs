@@ -0,0 +1,233 @@
+//! Streaming percentiles of durations.
+use crate::{parse_duration, Error};
+
+// Each power of two is split into 2^SUB_BITS buckets, which bounds the
+// relative error of reported percentiles to 2^-SUB_BITS (under 1%).
+const SUB_BITS: u32 = 7;
+const SUB_BUCKETS: u64 = 1 << SUB_BITS;
+
+/// DurationDigest summarizes a stream of durations, such as latencies read
+/// from logs, and reports their percentiles.
+///
+/// Durations are counted in logarithmic buckets, so memory use is bounded no
+/// matter how many are added, and reported percentiles are within 1% of the
+/// exact ones. Negative durations are counted as zero.
+///
+/// ```rust
+/// use go_parse_duration::DurationDigest;
+///
+/// let mut digest = DurationDigest::new();
+/// for s in &["120ms", "80ms", "95ms", "2s"] {
+///     digest.add_str(s).unwrap();
+/// }
+/// assert_eq!(digest.count(), 4);
+/// assert_eq!(digest.max(), Some(2000000000));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DurationDigest {
+    buckets: Vec<u64>,
+    count: u64,
+    min: i64,
+    max: i64,
+}
+
+impl DurationDigest {
+    /// new returns an empty digest.
+    pub fn new() -> DurationDigest {
+        DurationDigest::default()
+    }
+
+    /// add adds a duration in nanoseconds to the digest.
+    pub fn add(&mut self, ns: i64) {
+        let ns = ns.max(0);
+        let i = bucket(ns as u64);
+        if i >= self.buckets.len() {
+            self.buckets.resize(i + 1, 0);
+        }
+        self.buckets[i] += 1;
+        if self.count == 0 {
+            self.min = ns;
+            self.max = ns;
+        } else {
+            self.min = self.min.min(ns);
+            self.max = self.max.max(ns);
+        }
+        self.count += 1;
+    }
+
+    /// add_str parses a duration string and adds it to the digest.
+    pub fn add_str(&mut self, s: &str) -> Result<(), Error> {
+        self.add(parse_duration(s)?);
+        Ok(())
+    }
+
+    /// merge adds all the durations counted by other to the digest.
+    pub fn merge(&mut self, other: &DurationDigest) {
+        if other.count == 0 {
+            return;
+        }
+        if other.buckets.len() > self.buckets.len() {
+            self.buckets.resize(other.buckets.len(), 0);
+        }
+        for (n, m) in self.buckets.iter_mut().zip(&other.buckets) {
+            *n += m;
+        }
+        if self.count == 0 {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.count += other.count;
+    }
+
+    /// count returns the number of durations added.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// min returns the smallest duration added, exactly.
+    pub fn min(&self) -> Option<i64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.min)
+    }
+
+    /// max returns the largest duration added, exactly.
+    pub fn max(&self) -> Option<i64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.max)
+    }
+
+    /// quantile returns the duration below which the fraction q of the added
+    /// durations fall, e.g. 0.99 for the 99th percentile, or None if the
+    /// digest is empty.
+    ///
+    /// q is clamped to the range 0 to 1, where 0 and 1 give the exact
+    /// minimum and maximum.
+    pub fn quantile(&self, q: f64) -> Option<i64> {
+        if self.count == 0 {
+            return None;
+        }
+        let q = if q.is_nan() { 0.0 } else { q.clamp(0.0, 1.0) };
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        // The extremes are tracked exactly.
+        if rank == 1 {
+            return Some(self.min);
+        }
+        if rank >= self.count {
+            return Some(self.max);
+        }
+        let mut seen = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                let (lo, hi) = bounds(i);
+                let mid = (lo + (hi - lo) / 2) as i64;
+                return Some(mid.clamp(self.min, self.max));
+            }
+        }
+        Some(self.max)
+    }
+
+    /// p50 returns the median duration.
+    pub fn p50(&self) -> Option<i64> {
+        self.quantile(0.5)
+    }
+
+    /// p90 returns the 90th percentile duration.
+    pub fn p90(&self) -> Option<i64> {
+        self.quantile(0.9)
+    }
+
+    /// p99 returns the 99th percentile duration.
+    pub fn p99(&self) -> Option<i64> {
+        self.quantile(0.99)
+    }
+}
+
+// bucket returns the index of the bucket counting v. Values below
+// SUB_BUCKETS get a bucket each; larger ones share a bucket with the values
+// having the same SUB_BITS most significant bits.
+fn bucket(v: u64) -> usize {
+    if v < SUB_BUCKETS {
+        return v as usize;
+    }
+    let e = 63 - v.leading_zeros();
+    let shift = e - SUB_BITS;
+    let mantissa = v >> shift;
+    (SUB_BUCKETS + u64::from(shift) * SUB_BUCKETS + (mantissa - SUB_BUCKETS)) as usize
+}
+
+// bounds returns the smallest and largest values counted by bucket i.
+fn bounds(i: usize) -> (u64, u64) {
+    let i = i as u64;
+    if i < SUB_BUCKETS {
+        return (i, i);
+    }
+    let shift = (i - SUB_BUCKETS) / SUB_BUCKETS;
+    let mantissa = SUB_BUCKETS + (i - SUB_BUCKETS) % SUB_BUCKETS;
+    (mantissa << shift, ((mantissa + 1) << shift) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets() {
+        for &v in &[
+            0,
+            1,
+            127,
+            128,
+            129,
+            255,
+            256,
+            1000,
+            123456789,
+            i64::MAX as u64,
+        ] {
+            let (lo, hi) = bounds(bucket(v));
+            assert!(lo <= v && v <= hi, "{} not in {}..={}", v, lo, hi);
+        }
+        assert_eq!(bucket(255) + 1, bucket(256));
+    }
+
+    #[test]
+    fn test_digest() -> Result<(), Error> {
+        let mut digest = DurationDigest::new();
+        assert_eq!(digest.p50(), None);
+        for ms in 1..=1000 {
+            digest.add(ms * 1000000);
+        }
+        let near = |actual: Option<i64>, expected: i64| {
+            let actual = actual.unwrap();
+            assert!(
+                (actual - expected).abs() <= expected / 100,
+                "{} not within 1% of {}",
+                actual,
+                expected
+            );
+        };
+        near(digest.p50(), 500000000);
+        near(digest.p90(), 900000000);
+        near(digest.p99(), 990000000);
+        assert_eq!(digest.quantile(0.0), Some(1000000));
+        assert_eq!(digest.quantile(1.0), Some(1000000000));
+
+        let mut other = DurationDigest::new();
+        other.add_str("1h")?;
+        other.add(-5);
+        digest.merge(&other);
+        assert_eq!(digest.count(), 1002);
+        assert_eq!(digest.min(), Some(0));
+        assert_eq!(digest.max(), Some(3600000000000));
+        assert!(digest.add_str("1d").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,973 @@
+//! The [`GoDuration`] type, a duration in nanoseconds.
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+
+use crate::consts::{HOUR, MAX_DURATION, MICROSECOND, MILLISECOND, MINUTE, MIN_DURATION, SECOND};
+#[cfg(feature = "std")]
+use crate::Rounding;
+use crate::{format_duration, parse_duration, round, truncate, Error};
+
+/// GoDuration is a signed duration in nanoseconds, the same representation
+/// Go's `time.Duration` uses.
+///
+/// It displays as the Go duration string it parses from:
+///
+/// ```rust
+/// use go_parse_duration::GoDuration;
+///
+/// let d = GoDuration::parse("90m").unwrap();
+/// assert_eq!(d.as_nanos(), 5400000000000);
+/// assert_eq!(d.to_string(), "1h30m0s");
+/// ```
+///
+/// Durations are ordered from the most negative to the longest, so they can
+/// be bounded with `Ord::clamp`, `min` and `max`:
+///
+/// ```rust
+/// use go_parse_duration::GoDuration;
+///
+/// let min: GoDuration = "1s".parse().unwrap();
+/// let max: GoDuration = "30s".parse().unwrap();
+/// let timeout: GoDuration = "5m".parse().unwrap();
+/// assert_eq!(timeout.clamp(min, max), max);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GoDuration(i64);
+
+impl GoDuration {
+    /// ZERO is a duration of zero length.
+    pub const ZERO: GoDuration = GoDuration(0);
+
+    /// MAX is the longest duration, about 292 years.
+    pub const MAX: GoDuration = GoDuration(MAX_DURATION);
+
+    /// MIN is the most negative duration, about -292 years.
+    pub const MIN: GoDuration = GoDuration(MIN_DURATION);
+
+    /// parse parses a duration string like
+    /// [`parse_duration`](crate::parse_duration).
+    pub fn parse(s: &str) -> Result<GoDuration, Error> {
+        parse_duration(s).map(GoDuration)
+    }
+
+    /// from_nanos returns a duration of the given number of nanoseconds.
+    pub const fn from_nanos(ns: i64) -> GoDuration {
+        GoDuration(ns)
+    }
+
+    /// hours returns a duration of the given number of hours.
+    ///
+    /// ```rust
+    /// use go_parse_duration::GoDuration;
+    ///
+    /// let d = GoDuration::hours(1) + GoDuration::minutes(30);
+    /// assert_eq!(d, GoDuration::parse("1h30m").unwrap());
+    /// assert_eq!(GoDuration::hours(1).checked_mul(i64::MAX), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration overflows, in release builds too. Use
+    /// [`GoDuration::checked_mul`] on a duration of one hour to handle that
+    /// case instead.
+    pub const fn hours(n: i64) -> GoDuration {
+        from_unit(n, HOUR)
+    }
+
+    /// minutes returns a duration of the given number of minutes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration overflows, in release builds too.
+    pub const fn minutes(n: i64) -> GoDuration {
+        from_unit(n, MINUTE)
+    }
+
+    /// seconds returns a duration of the given number of seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration overflows, in release builds too.
+    pub const fn seconds(n: i64) -> GoDuration {
+        from_unit(n, SECOND)
+    }
+
+    /// millis returns a duration of the given number of milliseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration overflows, in release builds too.
+    pub const fn millis(n: i64) -> GoDuration {
+        from_unit(n, MILLISECOND)
+    }
+
+    /// micros returns a duration of the given number of microseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration overflows, in release builds too.
+    pub const fn micros(n: i64) -> GoDuration {
+        from_unit(n, MICROSECOND)
+    }
+
+    /// as_nanos returns the duration in nanoseconds.
+    pub const fn as_nanos(self) -> i64 {
+        self.0
+    }
+
+    /// as_micros returns the number of whole microseconds in the duration,
+    /// truncated toward zero.
+    pub const fn as_micros(self) -> i64 {
+        self.0 / MICROSECOND
+    }
+
+    /// as_millis returns the number of whole milliseconds in the duration,
+    /// truncated toward zero.
+    pub const fn as_millis(self) -> i64 {
+        self.0 / MILLISECOND
+    }
+
+    /// as_secs returns the number of whole seconds in the duration,
+    /// truncated toward zero, so -1.5s is -1.
+    pub const fn as_secs(self) -> i64 {
+        self.0 / SECOND
+    }
+
+    /// as_minutes returns the number of whole minutes in the duration,
+    /// truncated toward zero.
+    pub const fn as_minutes(self) -> i64 {
+        self.0 / MINUTE
+    }
+
+    /// as_hours returns the number of whole hours in the duration, truncated
+    /// toward zero.
+    pub const fn as_hours(self) -> i64 {
+        self.0 / HOUR
+    }
+
+    /// subsec_nanos returns the part of the duration after its whole
+    /// seconds, in nanoseconds, with the sign of the duration.
+    ///
+    /// ```rust
+    /// use go_parse_duration::GoDuration;
+    ///
+    /// let d = GoDuration::parse("-1.5s").unwrap();
+    /// assert_eq!((d.as_secs(), d.subsec_nanos()), (-1, -500000000));
+    /// ```
+    pub const fn subsec_nanos(self) -> i32 {
+        (self.0 % SECOND) as i32
+    }
+
+    /// subsec_micros returns the part of the duration after its whole
+    /// seconds, in whole microseconds, with the sign of the duration.
+    pub const fn subsec_micros(self) -> i32 {
+        self.subsec_nanos() / MICROSECOND as i32
+    }
+
+    /// subsec_millis returns the part of the duration after its whole
+    /// seconds, in whole milliseconds, with the sign of the duration.
+    pub const fn subsec_millis(self) -> i32 {
+        self.subsec_nanos() / MILLISECOND as i32
+    }
+
+    /// to_std returns the duration as a `std::time::Duration`, or None if
+    /// it's negative.
+    pub fn to_std(self) -> Option<Duration> {
+        if self.0 < 0 {
+            return None;
+        }
+        Some(Duration::from_nanos(self.0 as u64))
+    }
+
+    /// as_secs_f64 returns the duration in seconds as a float, as Go's
+    /// `Duration.Seconds()` does.
+    pub fn as_secs_f64(self) -> f64 {
+        // Converting the whole seconds and the nanoseconds separately keeps
+        // the nanoseconds exact for durations of up to 2^53 seconds.
+        let secs = self.0 / SECOND;
+        let nanos = self.0 % SECOND;
+        secs as f64 + nanos as f64 / 1e9
+    }
+
+    /// as_millis_f64 returns the duration in milliseconds as a float.
+    pub fn as_millis_f64(self) -> f64 {
+        let millis = self.0 / MILLISECOND;
+        let nanos = self.0 % MILLISECOND;
+        millis as f64 + nanos as f64 / 1e6
+    }
+
+    /// as_minutes_f64 returns the duration in minutes as a float, as Go's
+    /// `Duration.Minutes()` does.
+    pub fn as_minutes_f64(self) -> f64 {
+        let minutes = self.0 / MINUTE;
+        let nanos = self.0 % MINUTE;
+        minutes as f64 + nanos as f64 / (60.0 * 1e9)
+    }
+
+    /// as_hours_f64 returns the duration in hours as a float, as Go's
+    /// `Duration.Hours()` does.
+    pub fn as_hours_f64(self) -> f64 {
+        let hours = self.0 / HOUR;
+        let nanos = self.0 % HOUR;
+        hours as f64 + nanos as f64 / (60.0 * 60.0 * 1e9)
+    }
+
+    /// as_secs_f32 returns the duration in seconds as a float.
+    pub fn as_secs_f32(self) -> f32 {
+        self.as_secs_f64() as f32
+    }
+
+    #[cfg(feature = "std")]
+    /// try_from_secs_f64 returns a duration of the given number of seconds,
+    /// rounded to a whole nanosecond in the given mode. It's the inverse of
+    /// [`GoDuration::as_secs_f64`].
+    ///
+    /// ```rust
+    /// use go_parse_duration::{GoDuration, Rounding};
+    ///
+    /// let d = GoDuration::try_from_secs_f64(1.5, Rounding::Nearest).unwrap();
+    /// assert_eq!(d.as_nanos(), 1500000000);
+    /// let d = GoDuration::try_from_secs_f64(-1e-10, Rounding::Floor).unwrap();
+    /// assert_eq!(d.as_nanos(), -1);
+    /// ```
+    pub fn try_from_secs_f64(secs: f64, mode: Rounding) -> Result<GoDuration, TryFromSecsError> {
+        if secs.is_nan() {
+            return Err(TryFromSecsError::Nan);
+        }
+        if secs.is_infinite() {
+            return Err(TryFromSecsError::Infinite);
+        }
+        let ns = secs * 1e9;
+        let ns = match mode {
+            Rounding::Floor => ns.floor(),
+            Rounding::Ceil => ns.ceil(),
+            Rounding::Nearest => ns.round(),
+        };
+        // i64::MIN is exactly representable, i64::MAX rounds up to 2^63.
+        if ns < i64::MIN as f64 || ns >= i64::MAX as f64 {
+            return Err(TryFromSecsError::Overflow);
+        }
+        Ok(GoDuration(ns as i64))
+    }
+
+    #[cfg(feature = "std")]
+    /// try_from_secs_f32 is like [`GoDuration::try_from_secs_f64`] for f32.
+    pub fn try_from_secs_f32(secs: f32, mode: Rounding) -> Result<GoDuration, TryFromSecsError> {
+        GoDuration::try_from_secs_f64(f64::from(secs), mode)
+    }
+
+    /// is_zero reports whether the duration is zero.
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// is_negative reports whether the duration is less than zero.
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// signum returns -1, 0 or 1 depending on the sign of the duration.
+    pub const fn signum(self) -> i64 {
+        self.0.signum()
+    }
+
+    /// abs returns the absolute value of the duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration is `i64::MIN` nanoseconds, whose absolute value
+    /// doesn't fit, in release builds too. Use [`GoDuration::checked_abs`]
+    /// to handle that case instead.
+    pub fn abs(self) -> GoDuration {
+        self.checked_abs()
+            .expect("overflow when taking absolute duration")
+    }
+
+    /// checked_abs returns the absolute value of the duration, or None if it
+    /// overflows.
+    pub const fn checked_abs(self) -> Option<GoDuration> {
+        match self.0.checked_abs() {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// truncate returns the duration rounded toward zero to a multiple of m,
+    /// like [`truncate`](crate::truncate).
+    pub fn truncate(self, m: GoDuration) -> GoDuration {
+        GoDuration(truncate(self.0, m))
+    }
+
+    /// round returns the duration rounded to the nearest multiple of m,
+    /// halfway values away from zero, like [`round`](crate::round).
+    ///
+    /// ```rust
+    /// use go_parse_duration::GoDuration;
+    ///
+    /// let d = GoDuration::parse("1h15m30.918273645s").unwrap();
+    /// let m = GoDuration::parse("1m").unwrap();
+    /// assert_eq!(d.round(m).to_string(), "1h16m0s");
+    /// assert_eq!(d.truncate(m).to_string(), "1h15m0s");
+    /// ```
+    pub fn round(self, m: GoDuration) -> GoDuration {
+        GoDuration(round(self.0, m))
+    }
+
+    /// checked_add returns self + rhs, or None if it overflows.
+    pub const fn checked_add(self, rhs: GoDuration) -> Option<GoDuration> {
+        match self.0.checked_add(rhs.0) {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// checked_sub returns self - rhs, or None if it overflows.
+    pub const fn checked_sub(self, rhs: GoDuration) -> Option<GoDuration> {
+        match self.0.checked_sub(rhs.0) {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// checked_mul returns self * rhs, or None if it overflows.
+    pub const fn checked_mul(self, rhs: i64) -> Option<GoDuration> {
+        match self.0.checked_mul(rhs) {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// checked_div returns self / rhs, or None if rhs is zero or the
+    /// quotient overflows.
+    pub const fn checked_div(self, rhs: i64) -> Option<GoDuration> {
+        match self.0.checked_div(rhs) {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// checked_neg returns -self, or None if it overflows.
+    pub const fn checked_neg(self) -> Option<GoDuration> {
+        match self.0.checked_neg() {
+            Some(ns) => Some(GoDuration(ns)),
+            None => None,
+        }
+    }
+
+    /// saturating_add returns self + rhs, clamped to the bounds of a duration.
+    pub const fn saturating_add(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0.saturating_add(rhs.0))
+    }
+
+    /// saturating_sub returns self - rhs, clamped to the bounds of a duration.
+    pub const fn saturating_sub(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0.saturating_sub(rhs.0))
+    }
+
+    /// saturating_mul returns self * rhs, clamped to the bounds of a duration.
+    pub const fn saturating_mul(self, rhs: i64) -> GoDuration {
+        GoDuration(self.0.saturating_mul(rhs))
+    }
+
+    /// wrapping_add returns self + rhs, wrapping around at the bounds of a
+    /// duration.
+    pub const fn wrapping_add(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0.wrapping_add(rhs.0))
+    }
+
+    /// wrapping_sub returns self - rhs, wrapping around at the bounds of a
+    /// duration.
+    pub const fn wrapping_sub(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// wrapping_mul returns self * rhs, wrapping around at the bounds of a
+    /// duration.
+    pub const fn wrapping_mul(self, rhs: i64) -> GoDuration {
+        GoDuration(self.0.wrapping_mul(rhs))
+    }
+
+    /// wrapping_neg returns -self, wrapping around at the bounds of a
+    /// duration, so the negation of `i64::MIN` nanoseconds is itself.
+    pub const fn wrapping_neg(self) -> GoDuration {
+        GoDuration(self.0.wrapping_neg())
+    }
+
+    /// checked_sum adds up the durations of an iterator, returning None if
+    /// the total overflows.
+    pub fn checked_sum<I: IntoIterator<Item = GoDuration>>(iter: I) -> Option<GoDuration> {
+        iter.into_iter()
+            .try_fold(0i64, |total, d| total.checked_add(d.0))
+            .map(GoDuration)
+    }
+}
+
+// from_unit returns a duration of n units, panicking on overflow.
+const fn from_unit(n: i64, unit: i64) -> GoDuration {
+    match n.checked_mul(unit) {
+        Some(ns) => GoDuration(ns),
+        None => panic!("overflow when constructing duration"),
+    }
+}
+
+/// TryFromSecsError is the error returned when a number of seconds can't be
+/// converted to a [`GoDuration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryFromSecsError {
+    /// The number of seconds is NaN.
+    Nan,
+    /// The number of seconds is infinite.
+    Infinite,
+    /// The duration doesn't fit in an i64 of nanoseconds.
+    Overflow,
+}
+
+impl fmt::Display for TryFromSecsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryFromSecsError::Nan => write!(formatter, "duration seconds are NaN"),
+            TryFromSecsError::Infinite => write!(formatter, "duration seconds are infinite"),
+            TryFromSecsError::Overflow => write!(formatter, "duration overflows"),
+        }
+    }
+}
+
+impl core::error::Error for TryFromSecsError {}
+
+/// OverflowError is the error returned when a duration of another type
+/// doesn't fit in a [`GoDuration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "duration overflows")
+    }
+}
+
+impl core::error::Error for OverflowError {}
+
+impl From<i64> for GoDuration {
+    fn from(ns: i64) -> GoDuration {
+        GoDuration(ns)
+    }
+}
+
+impl From<GoDuration> for i64 {
+    fn from(d: GoDuration) -> i64 {
+        d.0
+    }
+}
+
+/// std durations longer than about 292 years don't fit in nanoseconds.
+impl TryFrom<Duration> for GoDuration {
+    type Error = OverflowError;
+
+    fn try_from(d: Duration) -> Result<GoDuration, OverflowError> {
+        i64::try_from(d.as_nanos())
+            .map(GoDuration)
+            .map_err(|_| OverflowError)
+    }
+}
+
+/// Durations parse from Go duration strings, so `"1h30m".parse()` works.
+impl FromStr for GoDuration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<GoDuration, Error> {
+        GoDuration::parse(s)
+    }
+}
+
+/// Durations display as Go's `Duration.String()` does, e.g. "1h30m0s", so
+/// they read the same in logs as those of Go services. Width, fill and
+/// alignment are honored, as in `format!("{:>8}", d)`.
+impl fmt::Display for GoDuration {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.pad(&format_duration(self.0))
+    }
+}
+
+/// Summing durations panics if the total overflows, in release builds too, as
+/// `std::time::Duration` does. Use [`GoDuration::checked_sum`] to handle
+/// overflow instead.
+impl Sum for GoDuration {
+    fn sum<I: Iterator<Item = GoDuration>>(iter: I) -> GoDuration {
+        GoDuration::checked_sum(iter).expect("overflow when summing durations")
+    }
+}
+
+impl<'a> Sum<&'a GoDuration> for GoDuration {
+    fn sum<I: Iterator<Item = &'a GoDuration>>(iter: I) -> GoDuration {
+        iter.copied().sum()
+    }
+}
+
+/// Collecting durations into a `GoDuration` adds them up, and like [`Sum`]
+/// panics if the total overflows. Use [`GoDuration::checked_sum`] to handle
+/// overflow instead.
+impl FromIterator<GoDuration> for GoDuration {
+    fn from_iter<I: IntoIterator<Item = GoDuration>>(iter: I) -> GoDuration {
+        iter.into_iter().sum()
+    }
+}
+
+impl<'a> FromIterator<&'a GoDuration> for GoDuration {
+    fn from_iter<I: IntoIterator<Item = &'a GoDuration>>(iter: I) -> GoDuration {
+        iter.into_iter().sum()
+    }
+}
+
+// The arithmetic operators behave like those of i64: they panic on overflow
+// in debug builds and wrap in release builds. The checked_, saturating_ and
+// wrapping_ methods handle overflow explicitly.
+
+impl Add for GoDuration {
+    type Output = GoDuration;
+
+    fn add(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for GoDuration {
+    fn add_assign(&mut self, rhs: GoDuration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for GoDuration {
+    type Output = GoDuration;
+
+    fn sub(self, rhs: GoDuration) -> GoDuration {
+        GoDuration(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for GoDuration {
+    fn sub_assign(&mut self, rhs: GoDuration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for GoDuration {
+    type Output = GoDuration;
+
+    fn neg(self) -> GoDuration {
+        GoDuration(-self.0)
+    }
+}
+
+impl Mul<i64> for GoDuration {
+    type Output = GoDuration;
+
+    fn mul(self, rhs: i64) -> GoDuration {
+        GoDuration(self.0 * rhs)
+    }
+}
+
+impl Mul<GoDuration> for i64 {
+    type Output = GoDuration;
+
+    fn mul(self, rhs: GoDuration) -> GoDuration {
+        rhs * self
+    }
+}
+
+impl MulAssign<i64> for GoDuration {
+    fn mul_assign(&mut self, rhs: i64) {
+        *self = *self * rhs;
+    }
+}
+
+/// Division truncates toward zero, and panics if the divisor is zero.
+impl Div<i64> for GoDuration {
+    type Output = GoDuration;
+
+    fn div(self, rhs: i64) -> GoDuration {
+        GoDuration(self.0 / rhs)
+    }
+}
+
+impl DivAssign<i64> for GoDuration {
+    fn div_assign(&mut self, rhs: i64) {
+        *self = *self / rhs;
+    }
+}
+
+/// range returns an iterator over the durations from start (inclusive) to
+/// end (exclusive) in increments of step.
+///
+/// It stands in for `std::iter::Step`, which can't be implemented on stable
+/// Rust.
+///
+/// ```rust
+/// use go_parse_duration::duration::range;
+/// use go_parse_duration::{parse_duration, Error, GoDuration};
+///
+/// fn schedule() -> Result<Vec<GoDuration>, Error> {
+///     let start = GoDuration::from_nanos(parse_duration("0s")?);
+///     let end = GoDuration::from_nanos(parse_duration("1m")?);
+///     let step = GoDuration::from_nanos(parse_duration("5s")?);
+///     Ok(range(start, end, step).collect())
+/// }
+///
+/// assert_eq!(schedule().unwrap().len(), 12);
+/// ```
+///
+/// # Panics
+///
+/// Panics if step is not positive.
+pub fn range(start: GoDuration, end: GoDuration, step: GoDuration) -> Range {
+    assert!(step.0 > 0, "duration range step must be positive");
+    Range {
+        next: Some(start.0),
+        end: end.0,
+        step: step.0,
+    }
+}
+
+/// Range is the iterator returned by [`range`].
+#[derive(Debug, Clone)]
+pub struct Range {
+    next: Option<i64>,
+    end: i64,
+    step: i64,
+}
+
+impl Iterator for Range {
+    type Item = GoDuration;
+
+    fn next(&mut self) -> Option<GoDuration> {
+        let d = self.next.filter(|&d| d < self.end)?;
+        // Stop rather than wrap if the next step overflows.
+        self.next = d.checked_add(self.step);
+        Some(GoDuration(d))
+    }
+}
+
+impl FusedIterator for Range {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use alloc::{format, vec};
+
+    #[test]
+    fn test_parse() -> Result<(), Error> {
+        assert_eq!(
+            GoDuration::parse("1h30m")?,
+            GoDuration::from_nanos(5400000000000)
+        );
+        assert_eq!(
+            GoDuration::parse("1d").unwrap_err(),
+            Error::UnknownUnit {
+                unit: String::from("d"),
+                duration: String::from("1d"),
+                span: crate::Span::new(1, 1),
+                suggestions: Vec::new(),
+            },
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str() -> Result<(), Error> {
+        let d: GoDuration = "1h30m".parse()?;
+        assert_eq!(d, GoDuration::from_nanos(5400000000000));
+        assert!("1x".parse::<GoDuration>().is_err());
+        let d = GoDuration::from_nanos(-1234567891);
+        assert_eq!(d.to_string().parse::<GoDuration>()?, d);
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GoDuration::from_nanos(5400000000000).to_string(), "1h30m0s");
+        assert_eq!(GoDuration::from_nanos(-1500).to_string(), "-1.5µs");
+        assert_eq!(format!("retry in {}", GoDuration::ZERO), "retry in 0s");
+        assert_eq!(format!("[{:>8}]", GoDuration::seconds(90)), "[   1m30s]");
+        assert_eq!(format!("[{:-<6}]", GoDuration::millis(5)), "[5ms---]");
+        for v in crate::test_vectors::VECTORS {
+            if let Some(d) = v.expected {
+                let d = GoDuration::from_nanos(d);
+                assert_eq!(d.to_string(), format_duration(d.as_nanos()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_std() {
+        let d = GoDuration::from_nanos(1500000000);
+        assert_eq!(d.to_std(), Some(Duration::from_millis(1500)));
+        assert_eq!(GoDuration::from_nanos(-1).to_std(), None);
+        assert_eq!(GoDuration::try_from(Duration::from_millis(1500)), Ok(d));
+        assert_eq!(
+            GoDuration::try_from(Duration::from_secs(u64::MAX)),
+            Err(OverflowError)
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let second = GoDuration::from_nanos(1000000000);
+        let steps: Vec<i64> = range(
+            GoDuration::from_nanos(0),
+            GoDuration::from_nanos(3500000000),
+            second,
+        )
+        .map(i64::from)
+        .collect();
+        assert_eq!(steps, vec![0, 1000000000, 2000000000, 3000000000]);
+        assert_eq!(range(second, second, second).count(), 0);
+        let max = GoDuration::from_nanos(i64::MAX);
+        assert_eq!(
+            range(GoDuration::from_nanos(i64::MAX - 1), max, second).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_sign() {
+        assert!(GoDuration::ZERO.is_zero());
+        assert!(!GoDuration::from_nanos(1).is_zero());
+        assert!(GoDuration::from_nanos(-1).is_negative());
+        assert!(!GoDuration::ZERO.is_negative());
+        assert_eq!(GoDuration::from_nanos(-5).signum(), -1);
+        assert_eq!(GoDuration::ZERO.signum(), 0);
+        assert_eq!(GoDuration::from_nanos(5).signum(), 1);
+        assert_eq!(GoDuration::from_nanos(-5).abs(), GoDuration::from_nanos(5));
+        assert_eq!(GoDuration::from_nanos(i64::MIN).checked_abs(), None);
+    }
+
+    #[test]
+    fn test_ops() -> Result<(), Error> {
+        let h = GoDuration::parse("1h")?;
+        let m = GoDuration::parse("1m")?;
+        assert_eq!((h + 30 * m).to_string(), "1h30m0s");
+        assert_eq!((h - m * 90).to_string(), "-30m0s");
+        assert_eq!((-h).to_string(), "-1h0m0s");
+        assert_eq!((h / 4).to_string(), "15m0s");
+        assert_eq!(GoDuration::from_nanos(-7) / 2, GoDuration::from_nanos(-3));
+        let mut d = h;
+        d += m;
+        d -= h;
+        d *= 3;
+        d /= 2;
+        assert_eq!(d.to_string(), "1m30s");
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let max = GoDuration::from_nanos(i64::MAX);
+        let min = GoDuration::from_nanos(i64::MIN);
+        let one = GoDuration::from_nanos(1);
+        assert_eq!(one.checked_add(one), Some(GoDuration::from_nanos(2)));
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(one.checked_div(0), None);
+        assert_eq!(min.checked_div(-1), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(max.checked_neg(), Some(min + one));
+
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(min.saturating_sub(one), min);
+        assert_eq!(max.saturating_mul(-2), min);
+        assert_eq!(one.saturating_mul(3), GoDuration::from_nanos(3));
+
+        assert_eq!(max.wrapping_add(one), min);
+        assert_eq!(min.wrapping_sub(one), max);
+        assert_eq!(max.wrapping_mul(2), GoDuration::from_nanos(-2));
+        assert_eq!(min.wrapping_neg(), min);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_add_overflow() {
+        let _ = GoDuration::from_nanos(i64::MAX) + GoDuration::from_nanos(1);
+    }
+
+    #[test]
+    fn test_ord() -> Result<(), Error> {
+        let mut durations: Vec<GoDuration> = ["1m", "-1s", "1s", "0", "1h"]
+            .iter()
+            .map(|s| s.parse())
+            .collect::<Result<_, _>>()?;
+        durations.sort();
+        let sorted: Vec<String> = durations.iter().map(|d| d.to_string()).collect();
+        assert_eq!(sorted, ["-1s", "0s", "1s", "1m0s", "1h0m0s"]);
+
+        let (lo, hi) = (GoDuration::parse("1s")?, GoDuration::parse("30s")?);
+        assert_eq!(GoDuration::parse("5m")?.clamp(lo, hi), hi);
+        assert_eq!(GoDuration::parse("-5m")?.clamp(lo, hi), lo);
+        assert_eq!(GoDuration::parse("10s")?.clamp(lo, hi).to_string(), "10s");
+        assert_eq!(lo.max(hi), hi);
+        assert_eq!(lo.min(hi), lo);
+        assert!(GoDuration::MIN < GoDuration::ZERO && GoDuration::ZERO < GoDuration::MAX);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_hash() -> Result<(), Error> {
+        let (lo, hi) = (GoDuration::parse("1s")?, GoDuration::parse("30s")?);
+        let set: std::collections::HashSet<GoDuration> = [lo, hi, GoDuration::parse("1000ms")?]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(set.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_round() -> Result<(), Error> {
+        // The example of Go's Duration.Round.
+        let d = GoDuration::parse("1h15m30.918273645s")?;
+        let rounded = [
+            ("1ns", "1h15m30.918273645s"),
+            ("1µs", "1h15m30.918274s"),
+            ("1ms", "1h15m30.918s"),
+            ("1s", "1h15m31s"),
+            ("2s", "1h15m30s"),
+            ("1m", "1h16m0s"),
+            ("10m", "1h20m0s"),
+            ("1h", "1h0m0s"),
+        ];
+        for &(m, s) in rounded.iter() {
+            assert_eq!(d.round(GoDuration::parse(m)?).to_string(), s, "{}", m);
+        }
+        assert_eq!(d.truncate(GoDuration::parse("1s")?).to_string(), "1h15m30s");
+        assert_eq!(d.truncate(GoDuration::ZERO), d);
+        assert_eq!(d.round(GoDuration::from_nanos(-1)), d);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_secs_f64() {
+        let d = |secs: f64, mode| GoDuration::try_from_secs_f64(secs, mode).map(i64::from);
+        assert_eq!(d(1.5, Rounding::Nearest), Ok(1500000000));
+        assert_eq!(d(-2.25, Rounding::Nearest), Ok(-2250000000));
+        assert_eq!(d(1.0000000005, Rounding::Floor), Ok(1000000000));
+        assert_eq!(d(1.0000000005, Rounding::Ceil), Ok(1000000001));
+        assert_eq!(d(-9223372036.0, Rounding::Floor), Ok(-9223372036000000000));
+        assert_eq!(d(f64::NAN, Rounding::Nearest), Err(TryFromSecsError::Nan));
+        assert_eq!(
+            d(f64::NEG_INFINITY, Rounding::Nearest),
+            Err(TryFromSecsError::Infinite)
+        );
+        assert_eq!(
+            d(9223372037.0, Rounding::Floor),
+            Err(TryFromSecsError::Overflow)
+        );
+        assert_eq!(
+            GoDuration::try_from_secs_f32(0.5, Rounding::Nearest),
+            Ok(GoDuration::from_nanos(500000000))
+        );
+        assert_eq!(GoDuration::from_nanos(1500000000).as_secs_f64(), 1.5);
+        assert_eq!(GoDuration::from_nanos(-250000000).as_secs_f32(), -0.25);
+    }
+
+    #[test]
+    fn test_constructors() -> Result<(), Error> {
+        assert_eq!(GoDuration::hours(2), GoDuration::parse("2h")?);
+        assert_eq!(GoDuration::minutes(-90), GoDuration::parse("-1h30m")?);
+        assert_eq!(GoDuration::seconds(45), GoDuration::parse("45s")?);
+        assert_eq!(GoDuration::millis(250), GoDuration::parse("250ms")?);
+        assert_eq!(GoDuration::micros(3), GoDuration::parse("3µs")?);
+        assert_eq!(
+            GoDuration::seconds(i64::MAX / 1000000000).as_secs(),
+            9223372036
+        );
+        const TIMEOUT: GoDuration = GoDuration::seconds(30);
+        assert_eq!(TIMEOUT.to_string(), "30s");
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when constructing duration")]
+    fn test_constructor_overflow() {
+        GoDuration::hours(2562048);
+    }
+
+    #[test]
+    fn test_accessors() -> Result<(), Error> {
+        let d = GoDuration::parse("1h15m30.918273645s")?;
+        assert_eq!(d.as_hours(), 1);
+        assert_eq!(d.as_minutes(), 75);
+        assert_eq!(d.as_secs(), 4530);
+        assert_eq!(d.as_millis(), 4530918);
+        assert_eq!(d.as_micros(), 4530918273);
+        assert_eq!(d.as_nanos(), 4530918273645);
+        assert_eq!(d.subsec_millis(), 918);
+        assert_eq!(d.subsec_micros(), 918273);
+        assert_eq!(d.subsec_nanos(), 918273645);
+        // The example of Go's Duration.Hours, Minutes and Seconds.
+        assert_eq!(GoDuration::parse("4h30m")?.as_hours_f64(), 4.5);
+        assert_eq!(GoDuration::parse("1h15m30s")?.as_minutes_f64(), 75.5);
+        assert_eq!(GoDuration::parse("1m30s")?.as_secs_f64(), 90.0);
+        assert_eq!(GoDuration::parse("1.5ms")?.as_millis_f64(), 1.5);
+
+        let d = GoDuration::parse("-1h1.5ms")?;
+        assert_eq!((d.as_hours(), d.as_secs()), (-1, -3600));
+        assert_eq!((d.subsec_millis(), d.subsec_micros()), (-1, -1500));
+        assert_eq!(GoDuration::MIN.as_secs(), -9223372036);
+        assert_eq!(GoDuration::MIN.subsec_nanos(), -854775808);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum() {
+        let durations = [1000000000, 500000000, -250000000].map(GoDuration::from_nanos);
+        assert_eq!(
+            durations.iter().sum::<GoDuration>(),
+            GoDuration::from_nanos(1250000000)
+        );
+        assert_eq!(
+            durations.iter().copied().sum::<GoDuration>(),
+            GoDuration::from_nanos(1250000000)
+        );
+        assert_eq!(
+            GoDuration::checked_sum([i64::MAX, 1].map(GoDuration::from_nanos)),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when summing durations")]
+    fn test_sum_overflow() {
+        let _: GoDuration = [i64::MAX, 1].map(GoDuration::from_nanos).iter().sum();
+    }
+
+    #[test]
+    fn test_collect() {
+        let durations = [1000000000, 500000000, -250000000].map(GoDuration::from_nanos);
+        assert_eq!(
+            durations.iter().collect::<GoDuration>(),
+            GoDuration::from_nanos(1250000000)
+        );
+        assert_eq!(
+            durations.iter().copied().collect::<GoDuration>(),
+            GoDuration::from_nanos(1250000000)
+        );
+        assert_eq!(
+            core::iter::empty::<GoDuration>().collect::<GoDuration>(),
+            GoDuration::from_nanos(0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when summing durations")]
+    fn test_collect_overflow() {
+        let _: GoDuration = [i64::MAX, 1].map(GoDuration::from_nanos).iter().collect();
+    }
+}
@@ -0,0 +1,451 @@
+//! Formatting durations as Go duration strings.
+use alloc::string::{String, ToString};
+
+use crate::consts::{HOUR, MICROSECOND, MILLISECOND, MINUTE, SECOND};
+use crate::{parse_duration, Error};
+
+/// format_duration returns a string representing the duration in the form
+/// "72h3m0.5s", exactly as Go's `Duration.String()` does.
+///
+/// Leading zero units are omitted. As a special case, durations less than one
+/// second use a smaller unit (milli-, micro-, or nanoseconds) to ensure that
+/// the leading digit is non-zero. The zero duration formats as 0s.
+///
+/// ```rust
+/// use go_parse_duration::format_duration;
+///
+/// assert_eq!(format_duration(6300000000000), "1h45m0s");
+/// assert_eq!(format_duration(300000000), "300ms");
+/// ```
+pub fn format_duration(d: i64) -> String {
+    DurationFormatter::new().format(d)
+}
+
+/// canonicalize parses a duration string and formats it in the shortest
+/// form Go's syntax allows: the largest units first, as [`format_duration`]
+/// writes them, without the components that are zero.
+///
+/// ```rust
+/// use go_parse_duration::canonicalize;
+///
+/// assert_eq!(canonicalize("90m").unwrap(), "1h30m");
+/// assert_eq!(canonicalize("3600s").unwrap(), "1h");
+/// assert_eq!(canonicalize("1500ms").unwrap(), "1.5s");
+/// assert!(canonicalize("90 minutes").is_err());
+/// ```
+pub fn canonicalize(s: &str) -> Result<String, Error> {
+    let mut s = format_duration(parse_duration(s)?);
+    // Only the minutes and seconds of "1h0m0s" can be zero components.
+    if s.ends_with("m0s") {
+        s.truncate(s.len() - 2);
+    }
+    if let Some(i) = s.find("h0m") {
+        s.replace_range(i + 1..i + 3, "");
+    }
+    Ok(s)
+}
+
+/// MicroSign selects how the microsecond unit is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MicroSign {
+    /// "µs", with U+00B5 MICRO SIGN, as Go writes it.
+    Unicode,
+    /// "us", for systems that only handle ASCII.
+    Ascii,
+}
+
+/// DurationFormatter formats durations like [`format_duration`], with
+/// options configured through its builder methods.
+///
+/// ```rust
+/// use go_parse_duration::format::{DurationFormatter, MicroSign};
+///
+/// let formatter = DurationFormatter::new().micro_sign(MicroSign::Ascii);
+/// assert_eq!(formatter.format(1500), "1.5us");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurationFormatter {
+    micro_sign: MicroSign,
+    si_seconds: bool,
+}
+
+impl DurationFormatter {
+    /// new returns a formatter producing exactly what Go does.
+    pub fn new() -> DurationFormatter {
+        DurationFormatter {
+            micro_sign: MicroSign::Unicode,
+            si_seconds: false,
+        }
+    }
+
+    /// micro_sign sets how the microsecond unit is written.
+    pub fn micro_sign(mut self, micro_sign: MicroSign) -> DurationFormatter {
+        self.micro_sign = micro_sign;
+        self
+    }
+
+    /// si_seconds makes durations of a second or more format as seconds with
+    /// an SI prefix, such as "90s", "1.2ks" or "3Ms", instead of hours,
+    /// minutes and seconds. They can be parsed back with
+    /// [`UnitTable::with_si_seconds`](crate::UnitTable::with_si_seconds).
+    pub fn si_seconds(mut self, si_seconds: bool) -> DurationFormatter {
+        self.si_seconds = si_seconds;
+        self
+    }
+
+    /// format returns a string representing the duration.
+    pub fn format(&self, d: i64) -> String {
+        format(d, self)
+    }
+}
+
+impl Default for DurationFormatter {
+    fn default() -> DurationFormatter {
+        DurationFormatter::new()
+    }
+}
+
+/// format_verbose returns a string representing the duration in English
+/// words, such as "1 hour 45 minutes", with every non-zero unit from days
+/// down to nanoseconds.
+pub fn format_verbose(d: i64) -> String {
+    VerboseFormatter::new().format(d)
+}
+
+/// VerboseFormatter formats durations in English words like
+/// [`format_verbose`], with options configured through its builder methods.
+///
+/// ```rust
+/// use go_parse_duration::format::VerboseFormatter;
+///
+/// let formatter = VerboseFormatter::new().components(2);
+/// assert_eq!(formatter.format(93784000000000), "1 day 2 hours");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerboseFormatter {
+    components: usize,
+}
+
+// WORDS lists the units format_verbose writes, largest first.
+const WORDS: [(u64, &str); 7] = [
+    (24 * HOUR as u64, "day"),
+    (HOUR as u64, "hour"),
+    (MINUTE as u64, "minute"),
+    (SECOND as u64, "second"),
+    (MILLISECOND as u64, "millisecond"),
+    (MICROSECOND as u64, "microsecond"),
+    (1, "nanosecond"),
+];
+
+impl VerboseFormatter {
+    /// new returns a formatter writing every non-zero unit.
+    pub fn new() -> VerboseFormatter {
+        VerboseFormatter {
+            components: usize::MAX,
+        }
+    }
+
+    /// components sets the largest number of units written. The smaller
+    /// units are dropped, truncating the duration, e.g. 1h45m30s is "1 hour
+    /// 45 minutes" with two components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is zero.
+    pub fn components(mut self, n: usize) -> VerboseFormatter {
+        assert!(n > 0, "verbose formatter needs at least one component");
+        self.components = n;
+        self
+    }
+
+    /// format returns a string representing the duration.
+    pub fn format(&self, d: i64) -> String {
+        let mut u = d.unsigned_abs();
+        if u == 0 {
+            return String::from("0 seconds");
+        }
+        let mut s = String::new();
+        if d < 0 {
+            s.push('-');
+        }
+        let mut n = 0;
+        for &(unit, word) in WORDS.iter() {
+            let v = u / unit;
+            u %= unit;
+            if v == 0 {
+                continue;
+            }
+            if n > 0 {
+                s.push(' ');
+            }
+            s.push_str(&v.to_string());
+            s.push(' ');
+            s.push_str(word);
+            if v != 1 {
+                s.push('s');
+            }
+            n += 1;
+            if n == self.components {
+                break;
+            }
+        }
+        s
+    }
+}
+
+impl Default for VerboseFormatter {
+    fn default() -> VerboseFormatter {
+        VerboseFormatter::new()
+    }
+}
+
+fn format(d: i64, opts: &DurationFormatter) -> String {
+    // Largest time is 2540400h10m10.000000000s
+    let mut buf = [0u8; 32];
+    let mut w = buf.len();
+
+    let mut u = d.unsigned_abs();
+    let neg = d < 0;
+    if u < 1000000000 {
+        // Special case: if duration is smaller than a second,
+        // use smaller units, like 1.2ms
+        let prec;
+        if u == 0 {
+            return String::from("0s");
+        }
+        w -= 1;
+        buf[w] = b's';
+        w -= 1;
+        if u < 1000 {
+            // print nanoseconds
+            prec = 0;
+            buf[w] = b'n';
+        } else if u < 1000000 {
+            // print microseconds
+            prec = 3;
+            match opts.micro_sign {
+                MicroSign::Unicode => {
+                    // U+00B5 'µ' micro sign == 0xC2 0xB5
+                    w -= 1; // Need room for two bytes.
+                    buf[w..w + 2].copy_from_slice("µ".as_bytes());
+                }
+                MicroSign::Ascii => buf[w] = b'u',
+            }
+        } else {
+            // print milliseconds
+            prec = 6;
+            buf[w] = b'm';
+        }
+        let (_w, _u) = fmt_frac(&mut buf[..w], u, prec);
+        w = fmt_int(&mut buf[.._w], _u);
+    } else if opts.si_seconds {
+        w -= 1;
+        buf[w] = b's';
+        let prec = if u >= 1000000000000000 {
+            w -= 1;
+            buf[w] = b'M';
+            15
+        } else if u >= 1000000000000 {
+            w -= 1;
+            buf[w] = b'k';
+            12
+        } else {
+            9
+        };
+        let (_w, _u) = fmt_frac(&mut buf[..w], u, prec);
+        w = fmt_int(&mut buf[.._w], _u);
+    } else {
+        w -= 1;
+        buf[w] = b's';
+
+        let (_w, _u) = fmt_frac(&mut buf[..w], u, 9);
+        w = _w;
+        u = _u;
+
+        // u is now integer seconds
+        w = fmt_int(&mut buf[..w], u % 60);
+        u /= 60;
+
+        // u is now integer minutes
+        if u > 0 {
+            w -= 1;
+            buf[w] = b'm';
+            w = fmt_int(&mut buf[..w], u % 60);
+            u /= 60;
+
+            // u is now integer hours
+            // Stop at hours because days can be different lengths.
+            if u > 0 {
+                w -= 1;
+                buf[w] = b'h';
+                w = fmt_int(&mut buf[..w], u);
+            }
+        }
+    }
+
+    if neg {
+        w -= 1;
+        buf[w] = b'-';
+    }
+
+    String::from_utf8(buf[w..].to_vec()).unwrap()
+}
+
+// fmt_frac formats the fraction of v/10**prec (e.g., ".12345") into the
+// tail of buf, omitting trailing zeros. It omits the decimal point too when
+// the fraction is 0. It returns the index where the output bytes begin and
+// the value v/10**prec.
+fn fmt_frac(buf: &mut [u8], mut v: u64, prec: usize) -> (usize, u64) {
+    // Omit trailing zeros up to and including decimal point.
+    let mut w = buf.len();
+    let mut print = false;
+    for _ in 0..prec {
+        let digit = v % 10;
+        print = print || digit != 0;
+        if print {
+            w -= 1;
+            buf[w] = digit as u8 + b'0';
+        }
+        v /= 10;
+    }
+    if print {
+        w -= 1;
+        buf[w] = b'.';
+    }
+    (w, v)
+}
+
+// fmt_int formats v into the tail of buf. It returns the index where the
+// output begins.
+fn fmt_int(buf: &mut [u8], mut v: u64) -> usize {
+    let mut w = buf.len();
+    if v == 0 {
+        w -= 1;
+        buf[w] = b'0';
+    } else {
+        while v > 0 {
+            w -= 1;
+            buf[w] = (v % 10) as u8 + b'0';
+            v /= 10;
+        }
+    }
+    w
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(1), "1ns");
+        assert_eq!(format_duration(1100), "1.1µs");
+        assert_eq!(format_duration(2200000), "2.2ms");
+        assert_eq!(format_duration(3300000000), "3.3s");
+        assert_eq!(format_duration(6300000000000), "1h45m0s");
+        assert_eq!(format_duration(-5400000000000), "-1h30m0s");
+        assert_eq!(format_duration(i64::MAX), "2562047h47m16.854775807s");
+        assert_eq!(format_duration(i64::MIN), "-2562047h47m16.854775808s");
+    }
+
+    #[test]
+    fn test_go_parity() {
+        // durationTests from Go's time_test.go.
+        let tests: [(&str, i64); 11] = [
+            ("0s", 0),
+            ("1ns", 1),
+            ("1.1µs", 1100),
+            ("2.2ms", 2200000),
+            ("3.3s", 3300000000),
+            ("4m5s", 245000000000),
+            ("4m5.001s", 245001000000),
+            ("5h6m7.001s", 18367001000000),
+            ("8m0.000000001s", 480000000001),
+            ("2562047h47m16.854775807s", i64::MAX),
+            ("-2562047h47m16.854775808s", i64::MIN),
+        ];
+        for &(s, d) in tests.iter() {
+            assert_eq!(format_duration(d), s);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for v in crate::test_vectors::VECTORS {
+            if let Some(d) = v.expected {
+                assert_eq!(
+                    crate::parse_duration(&format_duration(d)),
+                    Ok(d),
+                    "{}",
+                    v.input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonicalize() -> Result<(), Error> {
+        assert_eq!(canonicalize("90m")?, "1h30m");
+        assert_eq!(canonicalize("1h0m5s")?, "1h5s");
+        assert_eq!(canonicalize("60m0.5s")?, "1h0.5s");
+        assert_eq!(canonicalize("10m")?, "10m");
+        assert_eq!(canonicalize("1h10m")?, "1h10m");
+        assert_eq!(canonicalize("-120s")?, "-2m");
+        assert_eq!(canonicalize("0.001s")?, "1ms");
+        assert_eq!(canonicalize("+0")?, "0s");
+        for v in crate::test_vectors::VECTORS {
+            if let Some(d) = v.expected {
+                assert_eq!(
+                    parse_duration(&canonicalize(v.input)?),
+                    Ok(d),
+                    "{}",
+                    v.input
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_verbose() {
+        assert_eq!(format_verbose(6300000000000), "1 hour 45 minutes");
+        assert_eq!(
+            format_verbose(93784000000000),
+            "1 day 2 hours 3 minutes 4 seconds"
+        );
+        assert_eq!(format_verbose(-1000001), "-1 millisecond 1 nanosecond");
+        assert_eq!(format_verbose(0), "0 seconds");
+        assert_eq!(
+            format_verbose(i64::MIN),
+            "-106751 days 23 hours 47 minutes 16 seconds 854 milliseconds 775 microseconds 808 nanoseconds"
+        );
+        let two = VerboseFormatter::new().components(2);
+        assert_eq!(two.format(6330000000000), "1 hour 45 minutes");
+        assert_eq!(two.format(3600000000001), "1 hour 1 nanosecond");
+        assert_eq!(
+            VerboseFormatter::new().components(1).format(5400000000000),
+            "1 hour"
+        );
+    }
+
+    #[test]
+    fn test_micro_sign() {
+        let ascii = DurationFormatter::new().micro_sign(MicroSign::Ascii);
+        assert_eq!(ascii.format(1100), "1.1us");
+        assert_eq!(ascii.format(-999999), "-999.999us");
+        assert_eq!(ascii.format(1100000), "1.1ms");
+        assert_eq!(DurationFormatter::new().format(1100), "1.1µs");
+    }
+
+    #[test]
+    fn test_si_seconds() {
+        let si = DurationFormatter::new().si_seconds(true);
+        assert_eq!(si.format(90000000000), "90s");
+        assert_eq!(si.format(1200000000000), "1.2ks");
+        assert_eq!(si.format(-3000000000000000), "-3Ms");
+        assert_eq!(si.format(999999999999), "999.999999999s");
+        assert_eq!(si.format(1500000), "1.5ms");
+        assert_eq!(si.format(i64::MIN), "-9223.372036854775808Ms");
+    }
+}
@@ -0,0 +1,190 @@
+//! A drop-in replacement for the `humantime_serde` crate, available with the
+//! `serde` feature.
+//!
+//! Switching over only takes changing the path in the serde attribute:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use std::time::Duration;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "go_parse_duration::humantime_serde")]
+//!     timeout: Duration,
+//!     #[serde(default, with = "go_parse_duration::humantime_serde")]
+//!     retry: Option<Duration>,
+//! }
+//! ```
+//!
+//! Values are deserialized with [`parse_duration`] and serialized with
+//! [`format_duration`], so they use Go's syntax rather than humantime's.
+//! Negative durations can't be represented and fail to deserialize.
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Error as _, Serialize, Serializer};
+
+use crate::{format_duration, parse_duration, Error};
+
+/// deserialize deserializes a `Duration` or `Option<Duration>` from a Go
+/// duration string, for use with `#[serde(with = "...")]`.
+pub fn deserialize<'a, T, D>(d: D) -> Result<T, D::Error>
+where
+    Serde<T>: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    Serde::deserialize(d).map(Serde::into_inner)
+}
+
+/// serialize serializes a `Duration` or `Option<Duration>` as a Go duration
+/// string, for use with `#[serde(with = "...")]`.
+pub fn serialize<T, S>(d: &T, s: S) -> Result<S::Ok, S::Error>
+where
+    for<'a> Serde<&'a T>: Serialize,
+    S: Serializer,
+{
+    Serde::from(d).serialize(s)
+}
+
+/// Serde wraps a value to (de)serialize it as a Go duration string.
+#[derive(Copy, Clone, Eq, Hash, PartialEq, Ord, PartialOrd, Default)]
+pub struct Serde<T>(T);
+
+impl<T> Serde<T> {
+    /// into_inner returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Serde<T> {
+    fn from(val: T) -> Serde<T> {
+        Serde(val)
+    }
+}
+
+impl<T> Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Serde<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<Duration> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Serde<Duration>, D::Error> {
+        struct V;
+
+        impl<'de2> Visitor<'de2> for V {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a duration")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+                let d = parse_duration(v).map_err(E::custom)?;
+                if d < 0 {
+                    return Err(E::custom(Error::NegativeDuration(String::from(v))));
+                }
+                Ok(Duration::from_nanos(d as u64))
+            }
+        }
+
+        d.deserialize_str(V).map(Serde)
+    }
+}
+
+impl<'de> Deserialize<'de> for Serde<Option<Duration>> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Serde<Option<Duration>>, D::Error> {
+        let v: Option<Serde<Duration>> = Deserialize::deserialize(d)?;
+        Ok(Serde(v.map(Serde::into_inner)))
+    }
+}
+
+impl Serialize for Serde<&Duration> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let ns = i64::try_from(self.0.as_nanos())
+            .map_err(|_| S::Error::custom("duration overflows i64 nanoseconds"))?;
+        format_duration(ns).serialize(s)
+    }
+}
+
+impl Serialize for Serde<Duration> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        Serde(&self.0).serialize(s)
+    }
+}
+
+impl Serialize for Serde<&Option<Duration>> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match *self.0 {
+            Some(ref d) => s.serialize_some(&Serde(d)),
+            None => s.serialize_none(),
+        }
+    }
+}
+
+impl Serialize for Serde<Option<Duration>> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        Serde(&self.0).serialize(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super")]
+        timeout: Duration,
+        #[serde(default, with = "super")]
+        retry: Option<Duration>,
+    }
+
+    #[test]
+    fn test_with() {
+        let c: Config = serde_json::from_str(r#"{"timeout": "1m30s"}"#).unwrap();
+        assert_eq!(
+            c,
+            Config {
+                timeout: Duration::from_secs(90),
+                retry: None
+            }
+        );
+        let c: Config = serde_json::from_str(r#"{"timeout": "1.5s", "retry": "250ms"}"#).unwrap();
+        assert_eq!(c.retry, Some(Duration::from_millis(250)));
+        assert_eq!(
+            serde_json::to_string(&c).unwrap(),
+            r#"{"timeout":"1.5s","retry":"250ms"}"#
+        );
+        assert!(serde_json::from_str::<Config>(r#"{"timeout": "-1s"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"timeout": "1"}"#).is_err());
+    }
+
+    #[test]
+    fn test_wrapper() {
+        let d: Serde<Duration> = serde_json::from_str(r#""2h""#).unwrap();
+        assert_eq!(*d, Duration::from_secs(7200));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""2h0m0s""#);
+        let none: Serde<Option<Duration>> = serde_json::from_str("null").unwrap();
+        assert_eq!(none.into_inner(), None);
+    }
+}
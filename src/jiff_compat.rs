@@ -0,0 +1,104 @@
+// Conversions between durations and jiff's, with the jiff feature.
+use std::convert::TryFrom;
+
+use jiff::{SignedDuration, Span, SpanRound, Unit};
+
+use crate::{parse_duration, Error, GoDuration, OverflowError};
+
+/// parse_signed_duration parses a duration string and returns it as a
+/// `jiff::SignedDuration`.
+///
+/// ```rust
+/// use go_parse_duration::parse_signed_duration;
+///
+/// assert_eq!(parse_signed_duration("1m"), Ok(jiff::SignedDuration::from_mins(1)));
+/// ```
+pub fn parse_signed_duration(s: &str) -> Result<SignedDuration, Error> {
+    parse_duration(s).map(SignedDuration::from_nanos)
+}
+
+/// parse_span parses a duration string and returns it as a `jiff::Span` of
+/// hours and smaller units.
+///
+/// ```rust
+/// use go_parse_duration::parse_span;
+///
+/// let span = parse_span("1h30m").unwrap();
+/// assert_eq!((span.get_hours(), span.get_minutes()), (1, 30));
+/// ```
+pub fn parse_span(s: &str) -> Result<Span, Error> {
+    let d = parse_duration(s)?;
+    Span::try_from(GoDuration::from_nanos(d)).map_err(|_| Error::overflow(s))
+}
+
+impl From<GoDuration> for SignedDuration {
+    fn from(d: GoDuration) -> SignedDuration {
+        SignedDuration::from_nanos(d.as_nanos())
+    }
+}
+
+/// jiff durations longer than about 292 years don't fit in nanoseconds.
+impl TryFrom<SignedDuration> for GoDuration {
+    type Error = OverflowError;
+
+    fn try_from(d: SignedDuration) -> Result<GoDuration, OverflowError> {
+        i64::try_from(d.as_nanos())
+            .map(GoDuration::from_nanos)
+            .map_err(|_| OverflowError)
+    }
+}
+
+/// The span is balanced into hours and smaller units, e.g. 90 minutes is 1
+/// hour and 30 minutes. Days aren't used since their length depends on the
+/// time zone.
+impl TryFrom<GoDuration> for Span {
+    type Error = OverflowError;
+
+    fn try_from(d: GoDuration) -> Result<Span, OverflowError> {
+        Span::try_from(SignedDuration::from(d))
+            .and_then(|span| span.round(SpanRound::new().largest(Unit::Hour)))
+            .map_err(|_| OverflowError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signed_duration() -> Result<(), Error> {
+        assert_eq!(
+            parse_signed_duration("1h30m")?,
+            SignedDuration::from_mins(90)
+        );
+        assert_eq!(
+            parse_signed_duration("-1.5us")?,
+            SignedDuration::from_nanos(-1500)
+        );
+        assert!(parse_signed_duration("1d").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_span() -> Result<(), Error> {
+        let span = parse_span("-26h3m4.5s")?;
+        assert_eq!(span.get_hours(), -26);
+        assert_eq!(span.get_minutes(), -3);
+        assert_eq!(span.get_seconds(), -4);
+        assert_eq!(span.get_milliseconds(), -500);
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversions() -> Result<(), OverflowError> {
+        let d = GoDuration::from_nanos(i64::MIN);
+        assert_eq!(GoDuration::try_from(SignedDuration::from(d)), Ok(d));
+        assert_eq!(
+            GoDuration::try_from(SignedDuration::from_hours(24 * 365 * 300)),
+            Err(OverflowError)
+        );
+        let span = Span::try_from(GoDuration::from_nanos(i64::MAX))?;
+        assert_eq!(span.get_hours(), 2562047);
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+//! Kubernetes duration fields, available with the `serde` feature.
+//!
+//! [`Duration`] (de)serializes as Kubernetes' `metav1.Duration` does, so
+//! controllers and operators can read custom resources with duration fields
+//! and report bad values the way kubectl does:
+//!
+//! ```rust
+//! use go_parse_duration::kubernetes::Duration;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Spec {
+//!     interval: Duration,
+//! }
+//!
+//! let spec: Spec = serde_json::from_str(r#"{"interval": "90s"}"#).unwrap();
+//! assert_eq!(spec.interval.0.as_secs(), 90);
+//!
+//! let err = serde_json::from_str::<Spec>(r#"{"interval": "5x"}"#).err().unwrap();
+//! assert!(err
+//!     .to_string()
+//!     .starts_with(r#"time: unknown unit "x" in duration "5x""#));
+//! ```
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{parse_duration, Error, GoDuration};
+
+/// Duration is a duration field of a Kubernetes object, which is a Go
+/// duration string in JSON.
+///
+/// It serializes with Go's `Duration.String()`, and deserializes like
+/// `metav1.Duration.UnmarshalJSON`: the errors have the messages of Go's
+/// `time.ParseDuration` and `encoding/json`, such as
+/// `time: invalid duration "1.5"`, and null is the empty string, which is
+/// invalid. It's deserialized with `deserialize_any`, so it needs a
+/// self-describing format such as JSON or YAML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(pub GoDuration);
+
+impl From<GoDuration> for Duration {
+    fn from(d: GoDuration) -> Duration {
+        Duration(d)
+    }
+}
+
+impl From<Duration> for GoDuration {
+    fn from(d: Duration) -> GoDuration {
+        d.0
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        struct V;
+
+        impl<'de2> Visitor<'de2> for V {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Go duration string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+                parse_duration(v)
+                    .map(|d| Duration(GoDuration::from_nanos(d)))
+                    .map_err(|e| E::custom(go_error(&e, v)))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Duration, E> {
+                self.visit_str("")
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Duration, E> {
+                self.visit_str("")
+            }
+
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Duration, E> {
+                Err(E::custom(type_error("bool")))
+            }
+
+            fn visit_i64<E: de::Error>(self, _: i64) -> Result<Duration, E> {
+                Err(E::custom(type_error("number")))
+            }
+
+            fn visit_u64<E: de::Error>(self, _: u64) -> Result<Duration, E> {
+                Err(E::custom(type_error("number")))
+            }
+
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Duration, E> {
+                Err(E::custom(type_error("number")))
+            }
+
+            fn visit_seq<A: SeqAccess<'de2>>(self, _: A) -> Result<Duration, A::Error> {
+                Err(de::Error::custom(type_error("array")))
+            }
+
+            fn visit_map<A: MapAccess<'de2>>(self, _: A) -> Result<Duration, A::Error> {
+                Err(de::Error::custom(type_error("object")))
+            }
+        }
+
+        d.deserialize_any(V)
+    }
+}
+
+// type_error returns the message of the error encoding/json returns when a
+// JSON value of the given kind is unmarshaled into a string.
+fn type_error(kind: &str) -> String {
+    format!(
+        "json: cannot unmarshal {} into Go value of type string",
+        kind
+    )
+}
+
+// go_error returns the message of the error Go's time.ParseDuration returns
+// for the duration string s.
+fn go_error(e: &Error, s: &str) -> String {
+    match e {
+        Error::MissingUnit { .. } => format!("time: missing unit in duration {}", quote(s)),
+        Error::UnknownUnit { unit, .. } => format!(
+            "time: unknown unit {} in duration {}",
+            quote(unit),
+            quote(s)
+        ),
+        _ => format!("time: invalid duration {}", quote(s)),
+    }
+}
+
+// quote quotes s as Go's time package does in its errors, escaping quotes
+// and backslashes and writing control and non-ASCII bytes as \x escapes.
+fn quote(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for b in s.bytes() {
+        match b {
+            b'"' | b'\\' => {
+                q.push('\\');
+                q.push(b as char);
+            }
+            b' '..=0x7f => q.push(b as char),
+            _ => q.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    q.push('"');
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration() {
+        let d: Duration = serde_json::from_str(r#""1h30m""#).unwrap();
+        assert_eq!(d, Duration(GoDuration::from_nanos(5400000000000)));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""1h30m0s""#);
+        let d = Duration::from(GoDuration::from_nanos(0));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""0s""#);
+        let err = |json| {
+            serde_json::from_str::<Duration>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        let cases = [
+            (r#""1""#, r#"time: missing unit in duration "1""#),
+            (r#""1d""#, r#"time: unknown unit "d" in duration "1d""#),
+            (r#""""#, r#"time: invalid duration """#),
+            (r#""1h.""#, r#"time: invalid duration "1h.""#),
+            (r#""9999999h""#, r#"time: invalid duration "9999999h""#),
+            (
+                r#""5\"\\µ""#,
+                r#"time: unknown unit "\"\\\xc2\xb5" in duration "5\"\\\xc2\xb5""#,
+            ),
+            ("null", r#"time: invalid duration """#),
+            (
+                "5",
+                "json: cannot unmarshal number into Go value of type string",
+            ),
+            (
+                "true",
+                "json: cannot unmarshal bool into Go value of type string",
+            ),
+            (
+                "[]",
+                "json: cannot unmarshal array into Go value of type string",
+            ),
+        ];
+        for (json, message) in cases {
+            assert!(err(json).starts_with(message), "{}: {}", json, err(json));
+        }
+    }
+}
@@ -13,12 +13,14 @@
 //! ## Example
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")] {
 //! use go_parse_duration::{parse_duration, Error};
 //!
 //! fn parse() -> Result<i64, Error> {
 //!   let d = parse_duration("300us")?;
 //!   Ok(d)
 //! }
+//! # }
 //! ```
 //!
 //! **Usage with Chrono**
@@ -26,6 +28,7 @@
 //! Converting to Chrono duration can be done easily:
 //!
 //! ```rust
+//! # #[cfg(feature = "alloc")] {
 //! use chrono::Duration;
 //! use go_parse_duration::{parse_duration, Error};
 //!
@@ -33,26 +36,498 @@
 //!   let d = parse_duration("1m")?;
 //!   Ok(Duration::nanoseconds(d))
 //! }
+//! # }
 //! ```
 //!
-use std::fmt;
+//! With the `chrono` feature, `parse_chrono` does this for you.
+//!
+//! ## Dialects
+//!
+//! Duration strings written in other grammars can be parsed with the types in
+//! the [`dialect`] module:
+//!
+//! ```rust
+//! # #[cfg(feature = "std")] {
+//! use go_parse_duration::dialect::{DurationDialect, Rails};
+//!
+//! assert_eq!(Rails.parse("2.hours"), Ok(7200000000000));
+//! # }
+//! ```
+//!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Range;
+#[cfg(feature = "alloc")]
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+pub mod arith;
+#[cfg(feature = "chrono")]
+mod chrono_compat;
+#[cfg(feature = "clap")]
+mod clap_compat;
+mod const_parse;
+pub mod consts;
+#[cfg(feature = "cxx")]
+pub mod cpp;
+#[cfg(feature = "std")]
+pub mod dialect;
+#[cfg(feature = "std")]
+pub mod digest;
+#[cfg(feature = "alloc")]
+pub mod duration;
+#[cfg(feature = "alloc")]
+pub mod format;
+#[cfg(feature = "serde")]
+pub mod humantime_serde;
+#[cfg(feature = "jiff")]
+mod jiff_compat;
+#[cfg(feature = "serde")]
+pub mod kubernetes;
+#[cfg(feature = "std")]
+pub mod map;
+#[cfg(feature = "napi")]
+pub mod node;
+#[cfg(feature = "os")]
+pub mod os;
+#[cfg(feature = "std")]
+pub mod parser;
+#[cfg(feature = "alloc")]
+pub mod parts;
+#[cfg(kani)]
+mod proofs;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod scale;
+pub mod scan;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+pub mod stopwatch;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+pub mod ticks;
+#[cfg(feature = "time")]
+mod time_compat;
+#[cfg(feature = "std")]
+pub mod timer;
+#[cfg(feature = "std")]
+pub mod token;
+#[cfg(feature = "alloc")]
+pub mod units;
+#[cfg(feature = "widestring")]
+pub mod utf16;
+#[cfg(feature = "alloc")]
+pub mod wide;
+
+#[cfg(feature = "alloc")]
+pub use arith::{align_to, div_rem, percent_of, ratio, round, truncate, Rounding};
+#[cfg(feature = "chrono")]
+pub use chrono_compat::parse_chrono;
+#[cfg(feature = "clap")]
+pub use clap_compat::DurationValueParser;
+pub use const_parse::parse_duration_const;
+pub use consts::{
+    HOUR, MAX_DURATION, MICROSECOND, MILLISECOND, MINUTE, MIN_DURATION, NANOSECOND, SECOND,
+};
+#[cfg(feature = "std")]
+pub use dialect::parse_iso8601;
+#[cfg(feature = "std")]
+pub use digest::DurationDigest;
+#[cfg(feature = "alloc")]
+pub use duration::{GoDuration, OverflowError, TryFromSecsError};
+#[cfg(feature = "alloc")]
+pub use format::{canonicalize, format_duration, format_verbose};
+#[cfg(feature = "jiff")]
+pub use jiff_compat::{parse_signed_duration, parse_span};
+#[cfg(feature = "std")]
+pub use map::{parse_duration_map, parse_duration_map_with};
+#[cfg(feature = "std")]
+pub use parser::{DurationParser, Profile};
+#[cfg(feature = "alloc")]
+pub use parts::{DurationComponents, DurationParts};
+#[cfg(feature = "rand")]
+pub use random::{sample_between, DurationRange};
+#[cfg(feature = "std")]
+pub use registry::DurationRegistry;
+#[cfg(feature = "std")]
+pub use scale::TimeScale;
+#[cfg(feature = "std")]
+pub use stopwatch::{format_elapsed, Stopwatch};
+#[cfg(feature = "time")]
+pub use time_compat::parse_time;
+#[cfg(feature = "std")]
+pub use token::{tokenize, Token, TokenKind};
+#[cfg(feature = "alloc")]
+pub use units::{complete_unit, suggest_unit, CalendarPolicy, Unit, UnitRegistry, UnitTable};
+#[cfg(feature = "alloc")]
+pub use wide::{format_duration_i128, parse_duration_i128};
+
+#[cfg(feature = "alloc")]
+/// Error is the error returned when a duration string can't be parsed.
+///
+/// The variants other than [`Error::ParseError`] hold the duration string,
+/// and those for malformed strings hold the [`Span`] of the part that's at
+/// fault too.
 #[derive(Debug, PartialEq)]
 pub enum Error {
+    /// The duration string is empty.
+    Empty,
+    /// The duration string isn't well-formed, such as "1h.s" or "h".
+    InvalidSyntax { duration: String, span: Span },
+    /// A number in the duration string has no unit.
+    MissingUnit { duration: String, span: Span },
+    /// A unit in the duration string isn't one the parser accepts. The
+    /// suggestions are accepted units close to it, closest first.
+    UnknownUnit {
+        unit: String,
+        duration: String,
+        span: Span,
+        suggestions: Vec<String>,
+    },
+    /// The duration doesn't fit in an i64 of nanoseconds.
+    Overflow { duration: String, span: Span },
+    /// The duration string is negative where only non-negative durations
+    /// are allowed.
+    NegativeDuration(String),
+    /// Any other error, such as a unit a dialect recognizes but can't
+    /// convert, with a message describing it.
     ParseError(String),
 }
 
+#[cfg(feature = "alloc")]
+impl Error {
+    /// span returns the location of the part of the duration string that
+    /// caused the error, if the error has one.
+    ///
+    /// ```rust
+    /// use go_parse_duration::parse_duration;
+    ///
+    /// let err = parse_duration("1h30x").unwrap_err();
+    /// assert_eq!(err.span().unwrap().range(), 4..5);
+    /// ```
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidSyntax { span, .. }
+            | Error::MissingUnit { span, .. }
+            | Error::UnknownUnit { span, .. }
+            | Error::Overflow { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// kind returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Empty => ErrorKind::Empty,
+            Error::InvalidSyntax { .. } => ErrorKind::InvalidSyntax,
+            Error::MissingUnit { .. } => ErrorKind::MissingUnit,
+            Error::UnknownUnit { .. } => ErrorKind::UnknownUnit,
+            Error::Overflow { .. } => ErrorKind::Overflow,
+            Error::NegativeDuration(_) => ErrorKind::NegativeDuration,
+            Error::ParseError(_) => ErrorKind::Other,
+        }
+    }
+
+    // with_suggestions fills in the suggestions of an unknown unit error
+    // from the names of the units that are accepted.
+    pub(crate) fn with_suggestions<'a, I>(mut self, names: I) -> Error
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        if let Error::UnknownUnit {
+            unit, suggestions, ..
+        } = &mut self
+        {
+            *suggestions = units::suggest(unit, names)
+                .into_iter()
+                .map(String::from)
+                .collect();
+        }
+        self
+    }
+}
+
+// These construct errors for dialects, which don't track where in the string
+// an error is.
+#[cfg(feature = "std")]
+impl Error {
+    // invalid_syntax returns the error for a malformed duration string,
+    // spanning all of it.
+    pub(crate) fn invalid_syntax(duration: &str) -> Error {
+        Error::InvalidSyntax {
+            duration: String::from(duration),
+            span: Span::new(0, duration.len()),
+        }
+    }
+
+    // missing_unit returns the error for a duration string with a number
+    // missing its unit, spanning all of it.
+    pub(crate) fn missing_unit(duration: &str) -> Error {
+        Error::MissingUnit {
+            duration: String::from(duration),
+            span: Span::new(0, duration.len()),
+        }
+    }
+
+    // unknown_unit_at returns the error for an unknown unit found at offset in
+    // the duration string.
+    pub(crate) fn unknown_unit_at(unit: &str, duration: &str, offset: usize) -> Error {
+        Error::UnknownUnit {
+            unit: String::from(unit),
+            duration: String::from(duration),
+            span: Span::new(offset, unit.len()),
+            suggestions: Vec::new(),
+        }
+    }
+
+    // unknown_unit returns the error for an unknown unit at its first
+    // occurrence in the duration string, which is where the tests put it.
+    #[cfg(test)]
+    pub(crate) fn unknown_unit(unit: &str, duration: &str) -> Error {
+        Error::unknown_unit_at(unit, duration, duration.find(unit).unwrap_or(0))
+    }
+
+    // overflow returns the error for a duration string that overflows,
+    // spanning all of it.
+    pub(crate) fn overflow(duration: &str) -> Error {
+        Error::Overflow {
+            duration: String::from(duration),
+            span: Span::new(0, duration.len()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
 impl fmt::Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Error::ParseError(message) = self;
-        write!(formatter, "Parse error: {}", message)
+        match self {
+            Error::Empty => write!(formatter, "Parse error: empty duration"),
+            Error::InvalidSyntax { duration, .. } => {
+                write!(formatter, "Parse error: invalid duration: {}", duration)
+            }
+            Error::MissingUnit { duration, .. } => {
+                write!(
+                    formatter,
+                    "Parse error: missing unit in duration: {}",
+                    duration
+                )
+            }
+            Error::UnknownUnit {
+                unit,
+                duration,
+                suggestions,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "Parse error: unknown unit {} in duration {}",
+                    unit, duration
+                )?;
+                match suggestions.split_last() {
+                    None => Ok(()),
+                    Some((last, [])) => write!(formatter, ", did you mean {}?", last),
+                    Some((last, rest)) => {
+                        write!(formatter, ", did you mean {} or {}?", rest.join(", "), last)
+                    }
+                }
+            }
+            Error::Overflow { duration, .. } => {
+                write!(formatter, "Parse error: invalid duration {}", duration)
+            }
+            Error::ParseError(message) => write!(formatter, "Parse error: {}", message),
+            Error::NegativeDuration(string) => {
+                write!(formatter, "Parse error: negative duration: {}", string)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for Error {}
+
+/// Span is the location of part of a duration string, as a byte offset and
+/// length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// new returns the span of len bytes starting at offset.
+    pub const fn new(offset: usize, len: usize) -> Span {
+        Span { offset, len }
+    }
+
+    /// range returns the span as a range of byte indices, for slicing the
+    /// duration string.
+    pub const fn range(self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+}
+
+pub(crate) enum InternalError {
+    Invalid(Span),
+    MissingUnit(Span),
+    UnknownUnit(Span),
+    Overflow(Span),
+}
+
+impl InternalError {
+    // into_ref returns the error for the given duration string.
+    pub(crate) fn into_ref(self, string: &str) -> ErrorRef<'_> {
+        let (kind, span) = match self {
+            InternalError::Invalid(span) if string.is_empty() => (ErrorKind::Empty, span),
+            InternalError::Invalid(span) => (ErrorKind::InvalidSyntax, span),
+            InternalError::MissingUnit(span) => (ErrorKind::MissingUnit, span),
+            InternalError::UnknownUnit(span) => (ErrorKind::UnknownUnit, span),
+            InternalError::Overflow(span) => (ErrorKind::Overflow, span),
+        };
+        ErrorRef {
+            kind,
+            duration: string,
+            span,
+        }
+    }
+
+    // into_error renders the error for the given duration string.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_error(self, string: &str) -> Error {
+        Error::from(self.into_ref(string))
     }
 }
 
-enum InternalError {
+/// ErrorKind is the kind of an [`Error`] or [`ErrorRef`], for matching on
+/// without the data they hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Empty,
+    InvalidSyntax,
+    MissingUnit,
+    UnknownUnit,
     Overflow,
+    NegativeDuration,
+    /// Any other error, such as those of dialects.
+    Other,
 }
 
+/// ErrorRef is a parse error that borrows the duration string rather than
+/// copying it, so returning one never allocates. It's returned by
+/// [`try_parse_duration`] and renders the same message as [`Error`] when
+/// displayed, without the suggestions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorRef<'a> {
+    kind: ErrorKind,
+    duration: &'a str,
+    span: Span,
+}
+
+impl<'a> ErrorRef<'a> {
+    /// kind returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// duration returns the duration string that failed to parse.
+    pub fn duration(&self) -> &'a str {
+        self.duration
+    }
+
+    /// span returns the location of the part of the duration string that
+    /// caused the error.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// unit returns the unknown unit of an [`ErrorKind::UnknownUnit`] error.
+    pub fn unit(&self) -> Option<&'a str> {
+        match self.kind {
+            ErrorKind::UnknownUnit => self.duration.get(self.span.range()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorRef<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let duration = self.duration;
+        match self.kind {
+            ErrorKind::Empty => write!(formatter, "Parse error: empty duration"),
+            ErrorKind::InvalidSyntax => {
+                write!(formatter, "Parse error: invalid duration: {}", duration)
+            }
+            ErrorKind::MissingUnit => write!(
+                formatter,
+                "Parse error: missing unit in duration: {}",
+                duration
+            ),
+            ErrorKind::UnknownUnit => write!(
+                formatter,
+                "Parse error: unknown unit {} in duration {}",
+                self.unit().unwrap_or_default(),
+                duration
+            ),
+            ErrorKind::Overflow => write!(formatter, "Parse error: invalid duration {}", duration),
+            ErrorKind::NegativeDuration => {
+                write!(formatter, "Parse error: negative duration: {}", duration)
+            }
+            ErrorKind::Other => write!(formatter, "Parse error: {}", duration),
+        }
+    }
+}
+
+impl core::error::Error for ErrorRef<'_> {}
+
+#[cfg(feature = "alloc")]
+impl From<ErrorRef<'_>> for Error {
+    fn from(e: ErrorRef<'_>) -> Error {
+        let duration = String::from(e.duration);
+        let span = e.span;
+        match e.kind {
+            ErrorKind::Empty => Error::Empty,
+            ErrorKind::InvalidSyntax => Error::InvalidSyntax { duration, span },
+            ErrorKind::MissingUnit => Error::MissingUnit { duration, span },
+            ErrorKind::UnknownUnit => Error::UnknownUnit {
+                unit: String::from(e.unit().unwrap_or_default()),
+                duration,
+                span,
+                suggestions: Vec::new(),
+            },
+            ErrorKind::Overflow => Error::Overflow { duration, span },
+            ErrorKind::NegativeDuration => Error::NegativeDuration(duration),
+            ErrorKind::Other => Error::ParseError(duration),
+        }
+    }
+}
+
+/// try_parse_duration parses a duration string like [`parse_duration`], but
+/// returns an [`ErrorRef`] borrowing the string on failure, so it never
+/// allocates. It's available without the `alloc` feature.
+///
+/// ```rust
+/// use go_parse_duration::{try_parse_duration, ErrorKind};
+///
+/// assert_eq!(try_parse_duration("1h30m"), Ok(5400000000000));
+/// let err = try_parse_duration("1h30x").unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::UnknownUnit);
+/// assert_eq!(err.unit(), Some("x"));
+/// ```
+pub fn try_parse_duration(string: &str) -> Result<i64, ErrorRef<'_>> {
+    parse(string, unit_nanos, &Syntax::GO).map_err(|e| e.into_ref(string))
+}
+
+#[cfg(feature = "alloc")]
 /// parse_duration parses a duration string and return duration in nanoseconds.
 ///
 /// A duration string is a possibly signed sequence of decimal numbers, each
@@ -61,150 +536,408 @@ enum InternalError {
 ///
 /// Valid time units are "ns", "us" (or "µs"), "ms", "s", "m", "h".
 pub fn parse_duration(string: &str) -> Result<i64, Error> {
+    parse(string, unit_nanos, &Syntax::GO).map_err(|e| {
+        e.into_error(string)
+            .with_suggestions(GO_UNITS.iter().map(|(name, _)| *name))
+    })
+}
+
+#[cfg(feature = "alloc")]
+/// parse_duration_secs_f64 parses a duration string and returns it in
+/// seconds, with any fraction of a second, like Go's `Duration.Seconds()`.
+///
+/// An f64 holds about 16 significant digits, so the result is exact to the
+/// nanosecond only for durations of up to about 104 days (2^53 ns); longer
+/// ones are rounded to the nearest value an f64 can hold. Durations are
+/// parsed to whole nanoseconds first, so fractions finer than that are
+/// truncated as by [`parse_duration`].
+///
+/// ```rust
+/// use go_parse_duration::parse_duration_secs_f64;
+///
+/// assert_eq!(parse_duration_secs_f64("1m30.5s"), Ok(90.5));
+/// assert_eq!(parse_duration_secs_f64("-250ms"), Ok(-0.25));
+/// ```
+pub fn parse_duration_secs_f64(string: &str) -> Result<f64, Error> {
+    parse_duration(string).map(|d| GoDuration::from_nanos(d).as_secs_f64())
+}
+
+#[cfg(feature = "alloc")]
+/// parse_std parses a duration string and returns it as a
+/// `std::time::Duration`.
+///
+/// `Duration` can't be negative, so negative durations are rejected with
+/// [`Error::NegativeDuration`] rather than clamped to zero.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use go_parse_duration::{parse_std, Error};
+///
+/// assert_eq!(parse_std("1.5s"), Ok(Duration::from_millis(1500)));
+/// assert_eq!(parse_std("-1s"), Err(Error::NegativeDuration(String::from("-1s"))));
+/// ```
+pub fn parse_std(s: &str) -> Result<Duration, Error> {
+    let d = parse_duration(s)?;
+    if d < 0 {
+        return Err(Error::NegativeDuration(String::from(s)));
+    }
+    Ok(Duration::from_nanos(d as u64))
+}
+
+/// is_valid_duration reports whether s is a valid duration string, i.e.
+/// whether [`parse_duration`] would succeed on it.
+///
+/// It never allocates, which makes it cheaper than checking the result of
+/// `parse_duration` when validating many values. [`try_parse_duration`]
+/// doesn't either, and returns the error too.
+pub fn is_valid_duration(s: &str) -> bool {
+    parse(s, unit_nanos, &Syntax::GO).is_ok()
+}
+
+#[cfg(feature = "alloc")]
+/// parse_duration_saturating parses a duration string like
+/// [`parse_duration`], but clamps durations that overflow to `i64::MAX`, or
+/// `i64::MIN` if negative, instead of returning an error. Malformed strings
+/// are still an error.
+///
+/// ```rust
+/// use go_parse_duration::parse_duration_saturating;
+///
+/// assert_eq!(parse_duration_saturating("1h30m"), Ok(5400000000000));
+/// assert_eq!(parse_duration_saturating("9999999h"), Ok(i64::MAX));
+/// assert_eq!(parse_duration_saturating("-9999999h"), Ok(i64::MIN));
+/// assert!(parse_duration_saturating("9999999h1x").is_err());
+/// ```
+pub fn parse_duration_saturating(string: &str) -> Result<i64, Error> {
+    let syntax = Syntax {
+        saturating: true,
+        ..Syntax::GO
+    };
+    parse(string, unit_nanos, &syntax).map_err(|e| {
+        e.into_error(string)
+            .with_suggestions(GO_UNITS.iter().map(|(name, _)| *name))
+    })
+}
+
+#[cfg(feature = "alloc")]
+/// parse_duration_partial parses the longest leading part of a string that's
+/// a valid duration, and returns it with the rest of the string, for
+/// durations embedded in larger grammars. Units are taken to end at the first
+/// character that isn't a letter, so "30s timeout" is 30s followed by
+/// " timeout".
+///
+/// It's an error if the string doesn't start with a valid duration, or if
+/// the duration it starts with overflows.
+///
+/// ```rust
+/// use go_parse_duration::parse_duration_partial;
+///
+/// assert_eq!(parse_duration_partial("30s timeout"), Ok((30000000000, " timeout")));
+/// assert_eq!(parse_duration_partial("1h30m,5m"), Ok((5400000000000, ",5m")));
+/// assert!(parse_duration_partial("timeout 30s").is_err());
+/// ```
+pub fn parse_duration_partial(string: &str) -> Result<(i64, &str), Error> {
+    let bytes = string.as_bytes();
+    let mut i = match bytes.first() {
+        Some(b'-') | Some(b'+') => 1,
+        _ => 0,
+    };
+    let digits = |i: usize| i + bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+    // Find the end of the components that are each [0-9]*(\.[0-9]*)?
+    // followed by a known unit, then parse them once.
+    let mut end = None;
+    let end = loop {
+        let start = i;
+        i = digits(i);
+        let mut number = i > start;
+        if bytes.get(i) == Some(&b'.') {
+            let dot = i + 1;
+            i = digits(dot);
+            number |= i > dot;
+        }
+        let unit = i;
+        i += string[i..]
+            .chars()
+            .take_while(|c| c.is_alphabetic())
+            .map(char::len_utf8)
+            .sum::<usize>();
+        if !number || unit_nanos(&string[unit..i]).is_none() {
+            // If the string doesn't start with a component, parsing the first
+            // one reports why, unless it's a bare "0". Without even a number
+            // or unit, the whole string is parsed instead.
+            break end.unwrap_or(if i == start { string.len() } else { i });
+        }
+        end = Some(i);
+    };
+    let d = parse(&string[..end], unit_nanos, &Syntax::GO).map_err(|e| {
+        e.into_error(string)
+            .with_suggestions(GO_UNITS.iter().map(|(name, _)| *name))
+    })?;
+    Ok((d, &string[end..]))
+}
+
+// Syntax holds the extensions to Go's duration syntax parse accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Syntax {
+    // Allow whitespace around components and between numbers and units.
+    pub(crate) whitespace: bool,
+    // Accept U+2212 MINUS SIGN as well as '-'.
+    pub(crate) unicode_minus: bool,
+    // Look units up ignoring ASCII case if there's no exact match.
+    pub(crate) case_insensitive: bool,
+    // The unit of a bare number such as "30", in nanoseconds.
+    pub(crate) default_unit: Option<i64>,
+    // Clamp durations that overflow to i64::MAX or i64::MIN.
+    pub(crate) saturating: bool,
+    // Compute fractions with integers rather than f64, as Go does.
+    pub(crate) exact_fractions: bool,
+}
+
+impl Syntax {
+    pub(crate) const GO: Syntax = Syntax {
+        whitespace: false,
+        unicode_minus: false,
+        case_insensitive: false,
+        default_unit: None,
+        saturating: false,
+        exact_fractions: false,
+    };
+}
+
+impl Default for Syntax {
+    fn default() -> Syntax {
+        Syntax::GO
+    }
+}
+
+// parse parses a Go duration string, looking up the length of its units with
+// the unit function.
+pub(crate) fn parse<F>(string: &str, unit: F, syntax: &Syntax) -> Result<i64, InternalError>
+where
+    F: Fn(&str) -> Option<i64>,
+{
+    parse_with(string, |s, i| unit(&s[..i]).map(|ns| (i, ns)), syntax)
+}
+
+// parse_with parses a Go duration string, matching its units with the unit
+// function. It's called with the rest of the string at each unit and the
+// length of the run of characters Go would take as the unit, and returns the
+// length of the unit it matched and its length in nanoseconds.
+pub(crate) fn parse_with<F>(string: &str, unit: F, syntax: &Syntax) -> Result<i64, InternalError>
+where
+    F: Fn(&str, usize) -> Option<(usize, i64)>,
+{
     // [-+]?([0-9]*(\.[0-9]*)?[a-z]+)+
     let mut s = string;
-    let mut d: i64 = 0; // duration to be returned
+    let mut d: u64 = 0; // magnitude of the duration to be returned
     let mut neg = false;
 
+    let skip_whitespace = |s: &str| -> usize {
+        if syntax.whitespace {
+            s.len()
+                - s.trim_start_matches(|c: char| c.is_ascii_whitespace())
+                    .len()
+        } else {
+            0
+        }
+    };
+    s = &s[skip_whitespace(s)..];
+
     // Consume [-+]?
-    if s != "" {
-        let c = s.chars().nth(0).unwrap();
-        if c == '-' || c == '+' {
-            neg = c == '-';
+    match s.as_bytes().first() {
+        Some(&c) if c == b'-' || c == b'+' => {
+            neg = c == b'-';
             s = &s[1..];
         }
+        _ if syntax.unicode_minus && s.starts_with('\u{2212}') => {
+            neg = true;
+            s = &s['\u{2212}'.len_utf8()..];
+        }
+        _ => {}
     }
     // Special case: if all that is left is "0", this is zero.
-    if s == "0" {
+    if s == "0" || syntax.whitespace && s.trim_end_matches(|c: char| c.is_ascii_whitespace()) == "0"
+    {
         return Ok(0);
     }
-    if s == "" {
-        return Err(Error::ParseError(format!("invalid duration: {}", string)));
+    // pos returns the offset of the rest of the string s.
+    let pos = |s: &str| string.len() - s.len();
+    if s.is_empty() {
+        return Err(InternalError::Invalid(Span::new(pos(s), 0)));
     }
-    while s != "" {
+    // The largest magnitude of the duration: that of i64::MIN if negative.
+    let limit = if neg { MAX_MAGNITUDE } else { i64::MAX as u64 };
+    let mut first = true;
+    // Whether the duration overflowed, when saturating.
+    let mut saturated = false;
+    while !s.is_empty() {
         // integers before, after decimal point
-        let mut v: i64;
-        let mut f: i64 = 0;
+        let v: u64;
+        let mut f: u64 = 0;
         // value = v + f / scale
         let mut scale: f64 = 1f64;
+        let start = pos(s);
 
         // The next character must be [0-9.]
-        let c = s.chars().nth(0).unwrap();
-        if !(c == '.' || '0' <= c && c <= '9') {
-            return Err(Error::ParseError(format!("invalid duration: {}", string)));
+        let c = s.as_bytes()[0];
+        if !(c == b'.' || c.is_ascii_digit()) {
+            let len = s.chars().next().map_or(1, char::len_utf8);
+            return Err(InternalError::Invalid(Span::new(start, len)));
         }
         // Consume [0-9]*
         let pl = s.len();
-        match leading_int(s) {
-            Ok((_v, _s)) => {
+        match scan::leading_int(s) {
+            Some((_v, i)) if _v <= limit => {
                 v = _v;
-                s = _s;
+                s = &s[i..];
             }
-            Err(_) => {
-                return Err(Error::ParseError(format!("invalid duration: {}", string)));
+            _ => {
+                let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+                if !syntax.saturating {
+                    return Err(InternalError::Overflow(Span::new(start, digits)));
+                }
+                saturated = true;
+                v = 0;
+                s = &s[digits..];
             }
         }
         let pre = pl != s.len(); // whether we consume anything before a period
 
         // Consume (\.[0-9]*)?
         let mut post = false;
-        if s != "" && s.chars().nth(0).unwrap() == '.' {
+        if s.starts_with('.') {
             s = &s[1..];
             let pl = s.len();
-            match leading_fraction(s) {
-                (f_, scale_, s_) => {
-                    f = f_;
-                    scale = scale_;
-                    s = s_;
-                }
-            }
+            let (f_, scale_, s_) = leading_fraction(s);
+            f = f_;
+            scale = scale_;
+            s = s_;
             post = pl != s.len();
         }
         if !pre && !post {
             // no digits (e.g. ".s" or "-.s")
-            return Err(Error::ParseError(format!("invalid duration: {}", string)));
+            return Err(InternalError::Invalid(Span::new(start, 1)));
         }
+        let number = Span::new(start, pos(s) - start);
 
         // Consume unit.
-        let mut i = 0;
-        while i < s.len() {
-            let c = s.chars().nth(i).unwrap();
-            if c == '.' || '0' <= c && c <= '9' {
-                break;
+        s = &s[skip_whitespace(s)..];
+        let i = s
+            .bytes()
+            .position(|c| {
+                c == b'.' || c.is_ascii_digit() || syntax.whitespace && c.is_ascii_whitespace()
+            })
+            .unwrap_or(s.len());
+        let (unit, end) = if i == 0 {
+            match syntax.default_unit {
+                // A bare number such as "30" takes the default unit.
+                Some(unit) if first && s.is_empty() => (unit, pos(s)),
+                _ => return Err(InternalError::MissingUnit(number)),
             }
-            i += 1;
-        }
-        if i == 0 {
-            return Err(Error::ParseError(format!(
-                "missing unit in duration: {}",
-                string
-            )));
-        }
-        let u = &s[..i];
-        s = &s[i..];
-        let unit = match u {
-            "ns" => 1i64,
-            "us" => 1000i64,
-            "µs" => 1000i64, // U+00B5 = micro symbol
-            "μs" => 1000i64, // U+03BC = Greek letter mu
-            "ms" => 1000000i64,
-            "s" => 1000000000i64,
-            "m" => 60000000000i64,
-            "h" => 3600000000000i64,
-            _ => {
-                return Err(Error::ParseError(format!(
-                    "unknown unit {} in duration {}",
-                    u, string
-                )));
+        } else {
+            match unit(s, i) {
+                Some((len, unit)) => {
+                    s = &s[len..];
+                    let end = pos(s);
+                    s = &s[skip_whitespace(s)..];
+                    (unit, end)
+                }
+                None => {
+                    return Err(InternalError::UnknownUnit(Span::new(pos(s), i)));
+                }
             }
         };
-        if v > (1 << 63 - 1) / unit {
-            // overflow
-            return Err(Error::ParseError(format!("invalid duration {}", string)));
-        }
-        v *= unit;
-        if f > 0 {
-            // f64 is needed to be nanosecond accurate for fractions of hours.
-            // v >= 0 && (f*unit/scale) <= 3.6e+12 (ns/h, h is the largest unit)
-            v += (f as f64 * (unit as f64 / scale)) as i64;
-            if v < 0 {
-                // overflow
-                return Err(Error::ParseError(format!("invalid duration {}", string)));
+        first = false;
+        let component = Span::new(start, end - start);
+        let v = component_magnitude(v, f, scale, unit, syntax.exact_fractions);
+        match v.and_then(|v| d.checked_add(v)).filter(|&d| d <= limit) {
+            Some(_d) => d = _d,
+            None if syntax.saturating => saturated = true,
+            None => {
+                return Err(InternalError::Overflow(component));
             }
         }
-        d += v;
-        if d < 0 {
-            // overflow
-            return Err(Error::ParseError(format!("invalid duration {}", string)));
-        }
+    }
+    if saturated {
+        return Ok(if neg { i64::MIN } else { i64::MAX });
     }
     if neg {
-        d = -d;
+        // The magnitude is at most 1<<63, whose negation is i64::MIN.
+        return Ok((d as i64).wrapping_neg());
     }
-    Ok(d)
+    Ok(d as i64)
 }
 
-// leading_int consumes the leading [0-9]* from s.
-fn leading_int(s: &str) -> Result<(i64, &str), InternalError> {
-    let mut x = 0;
-    let mut i = 0;
-    while i < s.len() {
-        let c = s.chars().nth(i).unwrap();
-        if c < '0' || c > '9' {
-            break;
-        }
-        if x > (1 << 63 - 1) / 10 {
-            return Err(InternalError::Overflow);
-        }
-        let d = i64::from(c.to_digit(10).unwrap());
-        x = x * 10 + d;
-        if x < 0 {
-            // overflow
-            return Err(InternalError::Overflow);
-        }
-        i += 1;
+// GO_UNITS lists the units Go accepts and their length in nanoseconds.
+pub(crate) const GO_UNITS: [(&str, i64); 8] = [
+    ("ns", NANOSECOND),
+    ("us", MICROSECOND),
+    ("µs", MICROSECOND), // U+00B5 = micro symbol
+    ("μs", MICROSECOND), // U+03BC = Greek letter mu
+    ("ms", MILLISECOND),
+    ("s", SECOND),
+    ("m", MINUTE),
+    ("h", HOUR),
+];
+
+// unit_nanos returns the length of a Go duration unit in nanoseconds.
+pub(crate) fn unit_nanos(u: &str) -> Option<i64> {
+    GO_UNITS
+        .iter()
+        .find(|(name, _)| *name == u)
+        .map(|&(_, ns)| ns)
+}
+
+// MAX_MAGNITUDE is the largest magnitude of a duration, that of i64::MIN.
+pub(crate) const MAX_MAGNITUDE: u64 = 1 << 63;
+
+// component_magnitude computes (v + f / scale) * unit in nanoseconds,
+// returning None if it's more than MAX_MAGNITUDE or the unit is negative.
+// With exact, the fraction is computed with integers and truncated exactly
+// rather than rounded through an f64.
+pub(crate) fn component_magnitude(
+    v: u64,
+    f: u64,
+    scale: f64,
+    unit: i64,
+    exact: bool,
+) -> Option<u64> {
+    let unit = u64::try_from(unit).ok()?;
+    let mut v = v.checked_mul(unit)?;
+    if f > 0 {
+        let f = if exact {
+            // scale is a power of ten of at most 10^20, which f64 holds
+            // exactly.
+            (u128::from(f) * u128::from(unit) / scale as u128) as u64
+        } else {
+            // f64 is needed to be nanosecond accurate for fractions of hours.
+            // v >= 0 && (f*unit/scale) <= 3.6e+12 (ns/h, h is the largest unit)
+            (f as f64 * (unit as f64 / scale)) as u64
+        };
+        v = v.checked_add(f)?;
+    }
+    if v > MAX_MAGNITUDE {
+        return None;
+    }
+    Some(v)
+}
+
+// component_nanos computes (v + f / scale) * unit in nanoseconds, returning
+// None on overflow.
+#[cfg(feature = "std")]
+pub(crate) fn component_nanos(v: i64, f: u64, scale: f64, unit: i64) -> Option<i64> {
+    let v = component_magnitude(u64::try_from(v).ok()?, f, scale, unit, false)?;
+    i64::try_from(v).ok()
+}
+
+// leading_int consumes the leading [0-9]* from s, or returns None if the
+// value doesn't fit an i64.
+#[cfg(feature = "std")]
+pub(crate) fn leading_int(s: &str) -> Option<(i64, &str)> {
+    match scan::leading_int(s) {
+        Some((x, i)) if x <= i64::MAX as u64 => Some((x as i64, &s[i..])),
+        _ => None,
     }
-    Ok((x, &s[i..]))
 }
 
 // leading_fraction consumes the leading [0-9]* from s.
@@ -213,40 +946,16 @@ fn leading_int(s: &str) -> Result<(i64, &str), InternalError> {
 // it just stops accumulating precision.
 //
 // It returns (value, scale, remainder) tuple.
-fn leading_fraction(s: &str) -> (i64, f64, &str) {
-    let mut i = 0;
-    let mut x = 0i64;
-    let mut scale = 1f64;
-    let mut overflow = false;
-    while i < s.len() {
-        let c = s.chars().nth(i).unwrap();
-        if c < '0' || c > '9' {
-            break;
-        }
-        if overflow {
-            continue;
-        }
-        if x > (1 << 63 - 1) / 10 {
-            // It's possible for overflow to give a positive number, so take care.
-            overflow = true;
-            continue;
-        }
-        let d = i64::from(c.to_digit(10).unwrap());
-        let y = x * 10 + d;
-        if y < 0 {
-            overflow = true;
-            continue;
-        }
-        x = y;
-        scale *= 10f64;
-        i += 1;
-    }
+pub(crate) fn leading_fraction(s: &str) -> (u64, f64, &str) {
+    let (x, scale, i) = scan::leading_fraction(s);
     (x, scale, &s[i..])
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
+    use alloc::format;
+    use alloc::string::{String, ToString};
 
     #[test]
     fn test_parse_duration() -> Result<(), Error> {
@@ -257,8 +966,264 @@ mod tests {
         assert_eq!(parse_duration("1h45m")?, 6300000000000);
         assert_eq!(
             parse_duration("1").unwrap_err(),
-            Error::ParseError(String::from("missing unit in duration: 1")),
+            Error::MissingUnit {
+                duration: String::from("1"),
+                span: Span::new(0, 1),
+            }
+        );
+        assert_eq!(parse_duration("1µs")?, 1000);
+        assert_eq!(parse_duration("0.000000000000000000001s")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_std() -> Result<(), Error> {
+        assert_eq!(parse_std("1.5s")?, Duration::from_millis(1500));
+        assert_eq!(parse_std("0")?, Duration::from_secs(0));
+        assert_eq!(parse_std("-0s")?, Duration::from_secs(0));
+        assert_eq!(
+            parse_std("-1s").unwrap_err(),
+            Error::NegativeDuration(String::from("-1s")),
+        );
+        assert!(parse_std("1x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_duration_secs_f64() -> Result<(), Error> {
+        assert_eq!(parse_duration_secs_f64("1.5s")?, 1.5);
+        assert_eq!(parse_duration_secs_f64("1h")?, 3600.0);
+        assert_eq!(parse_duration_secs_f64("1ns")?, 1e-9);
+        assert_eq!(parse_duration_secs_f64("-1m0.000000001s")?, -60.000000001);
+        assert_eq!(parse_duration_secs_f64("0")?, 0.0);
+        assert_eq!(parse_duration_secs_f64("1000000h0.5s")?, 3600000000.5);
+        assert!(parse_duration_secs_f64("1x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_duration() {
+        assert!(is_valid_duration("1h45m"));
+        assert!(is_valid_duration("-1.5h"));
+        assert!(is_valid_duration("0"));
+        assert!(is_valid_duration("300µs"));
+        assert!(!is_valid_duration(""));
+        assert!(!is_valid_duration("1"));
+        assert!(!is_valid_duration("1d"));
+        assert!(!is_valid_duration(".s"));
+        assert!(!is_valid_duration("9999999999h"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_std_error() {
+        fn parse(s: &str) -> Result<i64, Box<dyn std::error::Error>> {
+            Ok(parse_duration(s)?)
+        }
+        assert_eq!(parse("1s").unwrap(), 1000000000);
+        let err = parse("1x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error: unknown unit x in duration 1x"
+        );
+        assert!(err.source().is_none());
+        assert!(err.downcast_ref::<Error>().is_some());
+    }
+
+    #[test]
+    fn test_try_parse_duration() {
+        assert_eq!(try_parse_duration("-1.5h"), Ok(-5400000000000));
+        let err = try_parse_duration("1h30").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingUnit);
+        assert_eq!((err.duration(), err.span().range()), ("1h30", 2..4));
+        assert_eq!(err.unit(), None);
+        assert_eq!(try_parse_duration("").unwrap_err().kind(), ErrorKind::Empty);
+        for s in ["", "1", "1x", ".s", "9999999999h"] {
+            let err = try_parse_duration(s).unwrap_err();
+            assert_eq!(err.to_string(), parse_duration(s).unwrap_err().to_string());
+            assert_eq!(err.kind(), parse_duration(s).unwrap_err().kind());
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_saturating() -> Result<(), Error> {
+        assert_eq!(parse_duration_saturating("1h45m")?, 6300000000000);
+        assert_eq!(parse_duration_saturating("9999999h")?, i64::MAX);
+        assert_eq!(parse_duration_saturating("-9999999h")?, i64::MIN);
+        assert_eq!(
+            parse_duration_saturating("99999999999999999999ns")?,
+            i64::MAX
         );
+        assert_eq!(parse_duration_saturating("2562047h47m17s")?, i64::MAX);
+        assert_eq!(parse_duration_saturating("-0.5s9999999h1s")?, i64::MIN);
+        let err = parse_duration_saturating("9999999h1x").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnknownUnit);
+        assert_eq!(err.span(), Some(Span::new(9, 1)));
+        assert!(parse_duration_saturating("9999999h.").is_err());
         Ok(())
     }
+
+    #[test]
+    fn test_parse_duration_partial() -> Result<(), Error> {
+        assert_eq!(
+            parse_duration_partial("30s timeout")?,
+            (30000000000, " timeout")
+        );
+        assert_eq!(parse_duration_partial("-1.5h")?, (-5400000000000, ""));
+        assert_eq!(parse_duration_partial("1h30m;")?, (5400000000000, ";"));
+        assert_eq!(parse_duration_partial("1h.5")?, (3600000000000, ".5"));
+        assert_eq!(parse_duration_partial("1h30x")?, (3600000000000, "30x"));
+        assert_eq!(parse_duration_partial("1h30")?, (3600000000000, "30"));
+        assert_eq!(parse_duration_partial("2µs-")?, (2000, "-"));
+        assert_eq!(parse_duration_partial("0 later")?, (0, " later"));
+        assert_eq!(parse_duration_partial("0s0")?, (0, "0"));
+        assert_eq!(parse_duration_partial("1s1.2.3s")?, (1000000000, "1.2.3s"));
+        assert_eq!(parse_duration_partial("").unwrap_err(), Error::Empty);
+        assert_eq!(
+            parse_duration_partial("30 timeout").unwrap_err().span(),
+            Some(Span::new(0, 2))
+        );
+        assert_eq!(
+            parse_duration_partial("timeout").unwrap_err().span(),
+            Some(Span::new(0, 1))
+        );
+        assert_eq!(
+            parse_duration_partial("5hr later").unwrap_err().kind(),
+            ErrorKind::UnknownUnit
+        );
+        let err = parse_duration_partial("1s9999999999h later").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Overflow);
+        assert_eq!(err.span(), Some(Span::new(2, 11)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() {
+        let span = |s| parse_duration(s).unwrap_err().span().map(Span::range);
+        assert_eq!(parse_duration("").unwrap_err(), Error::Empty);
+        assert_eq!(
+            parse_duration("-").unwrap_err(),
+            Error::InvalidSyntax {
+                duration: String::from("-"),
+                span: Span::new(1, 0),
+            }
+        );
+        assert_eq!(span("1h.s"), Some(2..3));
+        assert_eq!(span("1h-30m"), Some(1..3));
+        assert_eq!(
+            parse_duration("1h30x").unwrap_err(),
+            Error::UnknownUnit {
+                unit: String::from("x"),
+                duration: String::from("1h30x"),
+                span: Span::new(4, 1),
+                suggestions: Vec::new(),
+            }
+        );
+        assert_eq!(span("1h30"), Some(2..4));
+        assert_eq!(span("1s9999999999h"), Some(2..13));
+        assert_eq!(span("99999999999999999999s"), Some(0..20));
+        assert_eq!(
+            parse_duration("9999999999h").unwrap_err().to_string(),
+            "Parse error: invalid duration 9999999999h"
+        );
+        assert_eq!(
+            parse_duration("1x").unwrap_err().to_string(),
+            "Parse error: unknown unit x in duration 1x"
+        );
+        assert_eq!(
+            parse_duration("1mn").unwrap_err().to_string(),
+            "Parse error: unknown unit mn in duration 1mn, did you mean m or ms?"
+        );
+        assert_eq!(
+            parse_duration("5hr").unwrap_err().to_string(),
+            "Parse error: unknown unit hr in duration 5hr, did you mean h?"
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_long() {
+        // Parsing is linear in the length of the string.
+        let s = "1ns".repeat(100000);
+        assert_eq!(parse_duration(&s), Ok(100000));
+        let s = format!("{}1s", "0".repeat(1000000));
+        assert_eq!(parse_duration(&s), Ok(1000000000));
+        let s = format!("1s{}", "x".repeat(1000000));
+        assert_eq!(try_parse_duration(&s).unwrap_err().span().len, 1000001);
+    }
+
+    #[test]
+    fn test_overflow_boundaries() {
+        let check = |s: &str, expected: Option<i64>| {
+            assert_eq!(parse_duration(s).ok(), expected, "{}", s);
+            assert_eq!(try_parse_duration(s).ok(), expected, "{}", s);
+            assert_eq!(const_parse::parse_const(s).ok(), expected, "{}", s);
+            assert_eq!(
+                parse_duration_i128(s).ok().filter(|_| expected.is_some()),
+                expected.map(i128::from),
+                "{}",
+                s
+            );
+            if expected.is_none() {
+                assert_eq!(parse_duration(s).unwrap_err().kind(), ErrorKind::Overflow);
+            }
+        };
+        // The largest and smallest multiple of every unit.
+        for &(name, unit) in GO_UNITS.iter() {
+            let (q, r) = (i64::MAX / unit, i64::MAX % unit);
+            let q1 = i128::from(q) + 1;
+            check(&format!("{}{}", q, name), Some(q * unit));
+            check(&format!("{}{}", q1, name), None);
+            check(&format!("-{}{}", q, name), Some(-q * unit));
+            if r + 1 == unit {
+                check(&format!("-{}{}", q1, name), Some(i64::MIN));
+                check(&format!("-{}{}", q1 + 1, name), None);
+            } else {
+                check(&format!("-{}{}", q1, name), None);
+            }
+        }
+        // Fractions reaching the bounds.
+        check("9223372036.854775807s", Some(i64::MAX));
+        check("9223372036.854775808s", None);
+        check("-9223372036.854775808s", Some(i64::MIN));
+        check("-9223372036.854775809s", None);
+        check("2562047h47m16.854775807s", Some(i64::MAX));
+        check("2562047h47m16.854775808s", None);
+        check("-2562047h47m16.854775808s", Some(i64::MIN));
+        check("-2562047h47m16.854775809s", None);
+        // Sums reaching the bounds.
+        check("9223372036854775806ns1ns", Some(i64::MAX));
+        check("9223372036854775807ns1ns", None);
+        check("-9223372036854775807ns1ns", Some(i64::MIN));
+        check("-9223372036854775808ns1ns", None);
+        check("-9223372036854775808ns0ns", Some(i64::MIN));
+        check("4611686018427387904ns4611686018427387903ns", Some(i64::MAX));
+        // Numbers overflowing a u64.
+        check("18446744073709551616ns", None);
+        check("-18446744073709551616ns", None);
+        check("99999999999999999999999999h", None);
+
+        assert_eq!(
+            parse_duration_saturating("9223372036854775808ns"),
+            Ok(i64::MAX)
+        );
+        assert_eq!(
+            parse_duration_saturating("-9223372036854775809ns"),
+            Ok(i64::MIN)
+        );
+        assert_eq!(
+            parse_duration_saturating("-9223372036854775808ns"),
+            Ok(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_sum_overflow() {
+        assert_eq!(
+            parse_duration("4611686018427387904ns4611686018427387904ns").unwrap_err(),
+            Error::Overflow {
+                duration: String::from("4611686018427387904ns4611686018427387904ns"),
+                span: Span::new(21, 21),
+            },
+        );
+    }
 }
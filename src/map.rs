@@ -0,0 +1,85 @@
+//! Scanning `key=value` lists of durations, as found in DSNs and driver
+//! option strings.
+use std::collections::HashMap;
+
+use crate::{parse_duration, Error};
+
+/// parse_duration_map parses a list of `name=duration` pairs separated by
+/// semicolons, such as "connect=5s;read=30s;write=30s", and returns a map of
+/// the names to durations in nanoseconds.
+///
+/// ```rust
+/// use go_parse_duration::parse_duration_map;
+///
+/// let timeouts = parse_duration_map("connect=5s;read=30s").unwrap();
+/// assert_eq!(timeouts["connect"], 5000000000);
+/// assert_eq!(timeouts["read"], 30000000000);
+/// ```
+pub fn parse_duration_map(s: &str) -> Result<HashMap<String, i64>, Error> {
+    parse_duration_map_with(s, ';', '=')
+}
+
+/// parse_duration_map_with is like [`parse_duration_map`] with custom
+/// separators between pairs and between names and durations, e.g. `','` and
+/// `':'` for "connect:5s,read:30s".
+///
+/// Whitespace around names and durations and empty pairs are ignored. If a
+/// name appears more than once, its last duration wins.
+pub fn parse_duration_map_with(
+    s: &str,
+    pair_separator: char,
+    key_separator: char,
+) -> Result<HashMap<String, i64>, Error> {
+    let mut map = HashMap::new();
+    for pair in s.split(pair_separator) {
+        if pair.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = match pair.find(key_separator) {
+            Some(i) => (
+                pair[..i].trim(),
+                pair[i + key_separator.len_utf8()..].trim(),
+            ),
+            None => {
+                return Err(Error::ParseError(format!(
+                    "missing duration in entry: {}",
+                    pair
+                )));
+            }
+        };
+        if key.is_empty() {
+            return Err(Error::ParseError(format!(
+                "missing name in entry: {}",
+                pair
+            )));
+        }
+        map.insert(String::from(key), parse_duration(value)?);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_map() -> Result<(), Error> {
+        let map = parse_duration_map("connect=5s;read=30s;write=1m30s;")?;
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["connect"], 5000000000);
+        assert_eq!(map["write"], 90000000000);
+
+        let map = parse_duration_map_with(" connect : 5s , read : 1s , read : 2s ", ',', ':')?;
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["read"], 2000000000);
+
+        assert!(parse_duration_map("")?.is_empty());
+        assert_eq!(
+            parse_duration_map("connect").unwrap_err(),
+            Error::ParseError(String::from("missing duration in entry: connect")),
+        );
+        assert!(parse_duration_map("=5s").is_err());
+        assert!(parse_duration_map("connect=5").is_err());
+        Ok(())
+    }
+}
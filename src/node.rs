@@ -0,0 +1,37 @@
+//! Node.js bindings built with [napi-rs](https://napi.rs), available with
+//! the `napi` feature.
+//!
+//! Re-exported from a `cdylib` crate (`pub use go_parse_duration::node::*;`)
+//! and loaded as a Node addon, they export:
+//!
+//! ```js
+//! parseDuration("1h30m"); // 5400000000000n
+//! formatDuration(5400000000000n); // "1h30m0s"
+//! ```
+//!
+//! Durations are BigInt nanoseconds, since Number can't hold every i64.
+use napi::bindgen_prelude::BigInt;
+use napi_derive::napi;
+
+/// parse_duration parses a Go duration string and returns duration in
+/// nanoseconds. It throws an Error with the parse error's message if the
+/// string is invalid.
+#[napi(js_name = "parseDuration")]
+pub fn parse_duration(s: String) -> napi::Result<BigInt> {
+    crate::parse_duration(&s)
+        .map(BigInt::from)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// format_duration returns a duration in nanoseconds as a Go duration
+/// string. It throws a RangeError if the duration doesn't fit an i64.
+#[napi(js_name = "formatDuration")]
+pub fn format_duration(d: BigInt) -> napi::Result<String> {
+    match d.get_i64() {
+        (d, true) => Ok(crate::format_duration(d)),
+        _ => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            "duration overflows an int64",
+        )),
+    }
+}
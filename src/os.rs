@@ -0,0 +1,104 @@
+//! Conversions to and from the `timespec` and `timeval` structs of the C
+//! library, for passing parsed durations to calls such as `nanosleep` and
+//! `select`.
+//!
+//! The structs are normalized the POSIX way: the sub-second field is always
+//! in `0..1000000000` (or `0..1000000`) and carries the sign of the seconds,
+//! so -1.5s is -2 seconds plus 500000000 nanoseconds.
+use libc::{time_t, timespec, timeval};
+
+use crate::consts::{MICROSECOND, SECOND};
+
+/// to_timespec converts a duration in nanoseconds to a `timespec`.
+///
+/// It returns None if the seconds don't fit the platform's `time_t`.
+///
+/// ```rust
+/// use go_parse_duration::os::to_timespec;
+/// use go_parse_duration::parse_duration;
+///
+/// let ts = to_timespec(parse_duration("-1.5s").unwrap()).unwrap();
+/// assert_eq!((ts.tv_sec, ts.tv_nsec), (-2, 500000000));
+/// ```
+pub fn to_timespec(ns: i64) -> Option<timespec> {
+    Some(timespec {
+        tv_sec: seconds(ns.div_euclid(SECOND))?,
+        tv_nsec: ns.rem_euclid(SECOND) as _,
+    })
+}
+
+/// from_timespec converts a `timespec` to a duration in nanoseconds,
+/// returning None if it overflows. The struct needn't be normalized.
+pub fn from_timespec(ts: &timespec) -> Option<i64> {
+    nanos(ts.tv_sec as i128 * SECOND as i128 + ts.tv_nsec as i128)
+}
+
+/// to_timeval converts a duration in nanoseconds to a `timeval`, rounding
+/// down to a whole microsecond.
+///
+/// It returns None if the seconds don't fit the platform's `time_t`.
+pub fn to_timeval(ns: i64) -> Option<timeval> {
+    let us = ns.div_euclid(MICROSECOND);
+    Some(timeval {
+        tv_sec: seconds(us.div_euclid(SECOND / MICROSECOND))?,
+        tv_usec: us.rem_euclid(SECOND / MICROSECOND) as _,
+    })
+}
+
+/// from_timeval converts a `timeval` to a duration in nanoseconds, returning
+/// None if it overflows. The struct needn't be normalized.
+pub fn from_timeval(tv: &timeval) -> Option<i64> {
+    nanos(tv.tv_sec as i128 * SECOND as i128 + tv.tv_usec as i128 * MICROSECOND as i128)
+}
+
+// seconds converts s to a time_t, which is 32 bits wide on some platforms.
+fn seconds(s: i64) -> Option<time_t> {
+    let t = s as time_t;
+    if t as i64 != s {
+        return None;
+    }
+    Some(t)
+}
+
+fn nanos(ns: i128) -> Option<i64> {
+    if ns < i64::MIN as i128 || ns > i64::MAX as i128 {
+        return None;
+    }
+    Some(ns as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timespec() {
+        let ts = to_timespec(1500000000).unwrap();
+        assert_eq!((ts.tv_sec, ts.tv_nsec), (1, 500000000));
+        let ts = to_timespec(-1).unwrap();
+        assert_eq!((ts.tv_sec, ts.tv_nsec), (-1, 999999999));
+        assert_eq!(from_timespec(&ts), Some(-1));
+        for &ns in &[0, 1, -1500000000, i64::MAX, i64::MIN] {
+            assert_eq!(from_timespec(&to_timespec(ns).unwrap()), Some(ns));
+        }
+        let ts = timespec {
+            tv_sec: time_t::MAX,
+            tv_nsec: 0,
+        };
+        assert_eq!(from_timespec(&ts), None);
+    }
+
+    #[test]
+    fn test_timeval() {
+        let tv = to_timeval(-1500000999).unwrap();
+        assert_eq!((tv.tv_sec, tv.tv_usec), (-2, 499999));
+        assert_eq!(from_timeval(&tv), Some(-1500001000));
+        let tv = to_timeval(2000000500).unwrap();
+        assert_eq!((tv.tv_sec, tv.tv_usec), (2, 0));
+        let tv = timeval {
+            tv_sec: 1,
+            tv_usec: 2500000,
+        };
+        assert_eq!(from_timeval(&tv), Some(3500000000));
+    }
+}
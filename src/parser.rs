@@ -0,0 +1,582 @@
+//! A configurable Go duration parser.
+use crate::consts::HOUR;
+use crate::{
+    align_to, format_duration, parse, parse_with, CalendarPolicy, DurationRegistry, Error,
+    Rounding, Syntax, TimeScale, UnitRegistry, UnitTable,
+};
+
+/// DurationParser parses Go duration strings like
+/// [`parse_duration`](crate::parse_duration), with optional extensions
+/// configured through its builder methods.
+///
+/// ```rust
+/// use go_parse_duration::{DurationParser, DurationRegistry};
+///
+/// let names = DurationRegistry::new().with("default-timeout", 30000000000);
+/// let parser = DurationParser::new().names(names);
+/// assert_eq!(parser.parse("default-timeout"), Ok(30000000000));
+/// assert_eq!(parser.parse("1m"), Ok(60000000000));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DurationParser {
+    units: UnitTable,
+    registry: Option<UnitRegistry>,
+    names: Option<DurationRegistry>,
+    syntax: Syntax,
+    non_negative: bool,
+    scale: TimeScale,
+    quantum: Option<(i64, Rounding)>,
+    max: Option<i64>,
+}
+
+/// Profile is a ready-made parser configuration, see
+/// [`DurationParser::profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Exactly what Go's `time.ParseDuration` accepts.
+    GoStrict,
+    /// Durations as humans write them in config files: Go's units plus "d"
+    /// and "w", the short aliases "sec", "secs", "min", "mins", "hr" and
+    /// "hrs", whitespace such as "1h 30m" or "2 hrs", and the Unicode minus
+    /// sign.
+    HumanConfig,
+    /// Go's syntax with the units common in operations tooling: "d", "w" and
+    /// "y" (365 days).
+    OpsExtended,
+}
+
+impl DurationParser {
+    /// new returns a parser that accepts exactly what `parse_duration` does.
+    pub fn new() -> DurationParser {
+        DurationParser::default()
+    }
+
+    /// profile returns a parser configured by one of the ready-made profiles.
+    ///
+    /// ```rust
+    /// use go_parse_duration::{DurationParser, Profile};
+    ///
+    /// let parser = DurationParser::profile(Profile::HumanConfig);
+    /// assert_eq!(parser.parse("1d 2 hrs"), Ok(93600000000000));
+    /// ```
+    pub fn profile(profile: Profile) -> DurationParser {
+        let day = 24 * HOUR;
+        match profile {
+            Profile::GoStrict => DurationParser::new(),
+            Profile::HumanConfig => DurationParser {
+                units: UnitTable::go().with_days_and_weeks().with_aliases(),
+                syntax: Syntax {
+                    whitespace: true,
+                    unicode_minus: true,
+                    ..Syntax::GO
+                },
+                ..DurationParser::new()
+            },
+            Profile::OpsExtended => DurationParser::new()
+                .units(UnitTable::go().with_days_and_weeks().with("y", 365 * day)),
+        }
+    }
+
+    /// units sets the table the units of duration strings are looked up in.
+    pub fn units(mut self, table: UnitTable) -> DurationParser {
+        self.units = table;
+        self
+    }
+
+    /// unit_registry makes the parser match units against a registry of
+    /// suffixes, longest first, instead of looking them up in its table.
+    /// The options that add units to the table and
+    /// [`DurationParser::case_insensitive`] have no effect then.
+    pub fn unit_registry(mut self, registry: UnitRegistry) -> DurationParser {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// names makes the parser resolve duration strings that are a name in
+    /// the registry to the named duration.
+    pub fn names(mut self, registry: DurationRegistry) -> DurationParser {
+        self.names = Some(registry);
+        self
+    }
+
+    /// allow_days makes the parser accept days ("d", 24 hours) and weeks
+    /// ("w", 7 days) as well as the units it already does.
+    ///
+    /// ```rust
+    /// use go_parse_duration::DurationParser;
+    ///
+    /// let parser = DurationParser::new().allow_days();
+    /// assert_eq!(parser.parse("1w2d12h"), Ok(820800000000000));
+    /// ```
+    pub fn allow_days(mut self) -> DurationParser {
+        self.units = self.units.with_days_and_weeks();
+        self
+    }
+
+    /// aliases makes the parser accept the short unit aliases "sec", "secs",
+    /// "min", "mins", "hr" and "hrs", as in "5mins" or "2hrs".
+    pub fn aliases(mut self) -> DurationParser {
+        self.units = self.units.with_aliases();
+        self
+    }
+
+    /// whitespace makes the parser accept ASCII whitespace around and
+    /// between components and between numbers and their units, as in
+    /// "1h 30m" or " 1 h 30 m ".
+    ///
+    /// ```rust
+    /// use go_parse_duration::DurationParser;
+    ///
+    /// let parser = DurationParser::new().whitespace();
+    /// assert_eq!(parser.parse("1 h 30 m"), Ok(5400000000000));
+    /// ```
+    pub fn whitespace(mut self) -> DurationParser {
+        self.syntax.whitespace = true;
+        self
+    }
+
+    /// words makes the parser accept the English names of Go's units,
+    /// singular and plural, and turns on [`DurationParser::whitespace`], so
+    /// input such as "2 hours", "1 minute" or "1 hour 30 minutes" from
+    /// form fields parses directly.
+    ///
+    /// ```rust
+    /// use go_parse_duration::DurationParser;
+    ///
+    /// let parser = DurationParser::new().words();
+    /// assert_eq!(parser.parse("1 hour 30 minutes"), Ok(5400000000000));
+    /// assert_eq!(parser.parse("90seconds"), Ok(90000000000));
+    /// ```
+    pub fn words(mut self) -> DurationParser {
+        self.units = self.units.with_words();
+        self.syntax.whitespace = true;
+        self
+    }
+
+    /// months_and_years makes the parser accept months ("mo") and years
+    /// ("y"), with lengths chosen by the policy.
+    pub fn months_and_years(mut self, policy: CalendarPolicy) -> DurationParser {
+        self.units = self.units.with_months_and_years(policy);
+        self
+    }
+
+    /// case_insensitive makes the parser match units ignoring ASCII case, as
+    /// in "5MS" or "1H", when they don't match a unit exactly. Exact matches
+    /// win, so "Ms" is still megaseconds in a table that has them.
+    pub fn case_insensitive(mut self) -> DurationParser {
+        self.syntax.case_insensitive = true;
+        self
+    }
+
+    /// exact_fractions makes the parser compute the nanoseconds of fractions
+    /// with integer arithmetic, truncating them exactly, instead of the f64
+    /// arithmetic Go uses. The results are then the same on every platform
+    /// and for every number of digits, but can differ from Go's by a
+    /// nanosecond where the f64 rounds up.
+    ///
+    /// ```rust
+    /// use go_parse_duration::{parse_duration, DurationParser};
+    ///
+    /// let parser = DurationParser::new().exact_fractions();
+    /// assert_eq!(parser.parse("0.3333333333333333333h"), Ok(1199999999999));
+    /// assert_eq!(parse_duration("0.3333333333333333333h"), Ok(1200000000000));
+    /// ```
+    pub fn exact_fractions(mut self) -> DurationParser {
+        self.syntax.exact_fractions = true;
+        self
+    }
+
+    /// default_unit makes the parser accept a bare number such as "30" or
+    /// "1.5", taking it to be in the given unit. Numbers without a unit in
+    /// durations of several components, such as "1m30", are still an error.
+    ///
+    /// ```rust
+    /// use go_parse_duration::{DurationParser, Unit};
+    ///
+    /// let parser = DurationParser::new().default_unit(Unit::Second);
+    /// assert_eq!(parser.parse("30"), Ok(30000000000));
+    /// assert_eq!(parser.parse("30ms"), Ok(30000000));
+    /// ```
+    pub fn default_unit<D: Into<i64>>(mut self, unit: D) -> DurationParser {
+        self.syntax.default_unit = Some(unit.into());
+        self
+    }
+
+    /// max makes the parser reject durations longer than limit, in
+    /// nanoseconds.
+    pub fn max<D: Into<i64>>(mut self, limit: D) -> DurationParser {
+        self.max = Some(limit.into());
+        self
+    }
+
+    /// unicode_minus makes the parser accept U+2212 MINUS SIGN ("−") as well
+    /// as `-`, as found in values copied from rendered documents.
+    pub fn unicode_minus(mut self) -> DurationParser {
+        self.syntax.unicode_minus = true;
+        self
+    }
+
+    /// non_negative makes the parser reject negative durations, including
+    /// ones with a leading `-` that evaluate to zero such as "-0s", with
+    /// [`Error::NegativeDuration`].
+    pub fn non_negative(mut self) -> DurationParser {
+        self.non_negative = true;
+        self
+    }
+
+    /// time_scale makes the parser multiply every duration it parses by the
+    /// scale, e.g. to shrink timeouts in tests.
+    pub fn time_scale(mut self, scale: TimeScale) -> DurationParser {
+        self.scale = scale;
+        self
+    }
+
+    /// quantize_to makes the parser round every duration it parses to a
+    /// multiple of step, after any time scale is applied, e.g. for storage
+    /// that only holds whole milliseconds.
+    ///
+    /// ```rust
+    /// use go_parse_duration::{DurationParser, Rounding, Unit};
+    ///
+    /// let parser = DurationParser::new().quantize_to(Unit::Millisecond, Rounding::Nearest);
+    /// assert_eq!(parser.parse("1.2345s"), Ok(1235000000));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if step is not positive.
+    pub fn quantize_to<D: Into<i64>>(mut self, step: D, mode: Rounding) -> DurationParser {
+        let step = step.into();
+        assert!(step > 0, "quantization step must be positive");
+        self.quantum = Some((step, mode));
+        self
+    }
+
+    /// parse parses a duration string and returns duration in nanoseconds.
+    pub fn parse(&self, s: &str) -> Result<i64, Error> {
+        let d = match self.names.as_ref().and_then(|names| names.get(s)) {
+            Some(d) => d,
+            None => match &self.registry {
+                Some(registry) => parse_with(s, |s, i| registry.unit_at(s, i), &self.syntax),
+                None => parse(s, |u| self.unit(u), &self.syntax),
+            }
+            .map_err(|e| {
+                let e = e.into_error(s);
+                match &self.registry {
+                    Some(registry) => e.with_suggestions(registry.units().map(|(name, _)| name)),
+                    None => e.with_suggestions(self.units.units().map(|(name, _)| name)),
+                }
+            })?,
+        };
+        if self.non_negative && (d < 0 || self.minus_sign(s)) {
+            return Err(Error::NegativeDuration(String::from(s)));
+        }
+        let d = self.scale.apply(d);
+        let d = match self.quantum {
+            Some((step, mode)) => d.and_then(|d| align_to(d, step, mode)),
+            None => d,
+        };
+        let d = d.ok_or_else(|| Error::overflow(s))?;
+        match self.max {
+            Some(max) if d > max => Err(Error::ParseError(format!(
+                "duration {} exceeds maximum {}",
+                s,
+                format_duration(max)
+            ))),
+            _ => Ok(d),
+        }
+    }
+
+    // minus_sign reports whether s starts with a minus sign the parser
+    // accepts, so that "-0s" counts as negative.
+    fn minus_sign(&self, s: &str) -> bool {
+        let s = s.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        s.starts_with('-') || (self.syntax.unicode_minus && s.starts_with('\u{2212}'))
+    }
+
+    // unit returns the length of a unit in nanoseconds, if the parser
+    // accepts it.
+    fn unit(&self, u: &str) -> Option<i64> {
+        self.units.get(u).or_else(|| {
+            if !self.syntax.case_insensitive {
+                return None;
+            }
+            self.units
+                .units()
+                .find(|(name, _)| name.eq_ignore_ascii_case(u))
+                .map(|(_, ns)| ns)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Span, Unit};
+
+    #[test]
+    fn test_parser() -> Result<(), Error> {
+        let parser = DurationParser::new();
+        assert_eq!(parser.parse("1h45m")?, 6300000000000);
+        assert_eq!(
+            parser.parse("1d").unwrap_err(),
+            Error::unknown_unit("d", "1d"),
+        );
+
+        let parser = DurationParser::new().units(UnitTable::go().with("d", 86400000000000));
+        assert_eq!(parser.parse("1d12h")?, 129600000000000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_allow_days() -> Result<(), Error> {
+        let parser = DurationParser::new().allow_days();
+        assert_eq!(parser.parse("7d")?, 604800000000000);
+        assert_eq!(parser.parse("2w")?, 1209600000000000);
+        assert_eq!(parser.parse("1.5d")?, 129600000000000);
+        assert_eq!(parser.parse("90m")?, 5400000000000);
+        assert!(parser.parse("1y").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_aliases() -> Result<(), Error> {
+        let parser = DurationParser::new().aliases();
+        assert_eq!(parser.parse("5mins")?, 300000000000);
+        assert_eq!(parser.parse("2hrs30min")?, 9000000000000);
+        assert_eq!(parser.parse("1.5secs")?, 1500000000);
+        assert_eq!(parser.parse("1hr")?, 3600000000000);
+        assert!(parser.parse("5minutes").is_err());
+        assert!(DurationParser::new().parse("5mins").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_registry() -> Result<(), Error> {
+        let units = UnitRegistry::new()
+            .with("tick", 100)
+            .with("slot", 12000000000)
+            .with("x2", 2);
+        let parser = DurationParser::new().unit_registry(units);
+        assert_eq!(parser.parse("3slot")?, 36000000000);
+        assert_eq!(parser.parse("1m5tick")?, 60000000500);
+        assert_eq!(parser.parse("3x2")?, 6);
+        assert_eq!(
+            parser.parse("1slots").unwrap_err().to_string(),
+            "Parse error: unknown unit slots in duration 1slots, did you mean slot?",
+        );
+        let parser = DurationParser::new().unit_registry(UnitRegistry::empty().with("tick", 100));
+        assert!(parser.parse("1s").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_case_insensitive() -> Result<(), Error> {
+        let parser = DurationParser::new().case_insensitive();
+        assert_eq!(parser.parse("5MS")?, 5000000);
+        assert_eq!(parser.parse("1H30M")?, 5400000000000);
+        assert_eq!(parser.parse("1h")?, 3600000000000);
+        let si = parser.units(UnitTable::go().with_si_seconds());
+        assert_eq!(si.parse("1Ms")?, 1000000000000000);
+        assert_eq!(si.parse("1mS")?, 1000000);
+        assert!(DurationParser::new().parse("5MS").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_fractions() -> Result<(), Error> {
+        let parser = DurationParser::new().exact_fractions();
+        assert_eq!(parser.parse("1.5h")?, 5400000000000);
+        assert_eq!(parser.parse("0.1s")?, 100000000);
+        assert_eq!(parser.parse("-1.000000001s")?, -1000000001);
+        assert_eq!(parser.parse("0.99999999999999999999h")?, 3599999999999);
+        assert_eq!(parser.parse("1.9999999999ns")?, 1);
+        assert_eq!(parser.parse("2562047h47m16.854775807s")?, i64::MAX);
+        assert_eq!(
+            parser.parse("2562047h47m16.854775808s").unwrap_err().kind(),
+            crate::ErrorKind::Overflow
+        );
+        for v in crate::test_vectors::VECTORS {
+            // The f64 rounds the only fraction in the vectors that isn't
+            // exact up by a nanosecond.
+            if v.input != "0.3333333333333333333h" {
+                assert_eq!(parser.parse(v.input).ok(), v.expected, "{}", v.input);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_unit() -> Result<(), Error> {
+        let parser = DurationParser::new().default_unit(Unit::Second);
+        assert_eq!(parser.parse("30")?, 30000000000);
+        assert_eq!(parser.parse("-1.5")?, -1500000000);
+        assert_eq!(parser.parse("0")?, 0);
+        assert_eq!(parser.parse("2m")?, 120000000000);
+        assert_eq!(
+            parser.parse("1m30").unwrap_err(),
+            Error::MissingUnit {
+                duration: String::from("1m30"),
+                span: Span::new(2, 2),
+            },
+        );
+        let parser = parser.whitespace();
+        assert_eq!(parser.parse(" 30 ")?, 30000000000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max() -> Result<(), Error> {
+        let parser = DurationParser::new().max(Unit::Hour);
+        assert_eq!(parser.parse("1h")?, 3600000000000);
+        assert_eq!(parser.parse("-2h")?, -7200000000000);
+        assert_eq!(
+            parser.parse("1h0m1s").unwrap_err(),
+            Error::ParseError(String::from("duration 1h0m1s exceeds maximum 1h0m0s")),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_whitespace() -> Result<(), Error> {
+        let parser = DurationParser::new().whitespace();
+        assert_eq!(parser.parse("1h 30m")?, 5400000000000);
+        assert_eq!(parser.parse("1 h 30 m")?, 5400000000000);
+        assert_eq!(parser.parse("\t-1.5 s\n")?, -1500000000);
+        assert_eq!(parser.parse(" 0 ")?, 0);
+        assert!(parser.parse("1 5s").is_err());
+        assert!(parser.parse("- 1s").is_err());
+        assert!(parser.parse("1ho ur").is_err());
+        assert!(DurationParser::new().parse("1h 30m").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_words() -> Result<(), Error> {
+        let parser = DurationParser::new().words();
+        assert_eq!(parser.parse("2 hours")?, 7200000000000);
+        assert_eq!(parser.parse("1 minute")?, 60000000000);
+        assert_eq!(parser.parse("90 seconds")?, 90000000000);
+        assert_eq!(parser.parse("1.5 milliseconds")?, 1500000);
+        assert_eq!(parser.parse("1h 30 minutes")?, 5400000000000);
+        assert!(parser.parse("2 hourz").is_err());
+        assert!(parser.parse("2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_months_and_years() -> Result<(), Error> {
+        let parser = DurationParser::new().months_and_years(CalendarPolicy::Average);
+        assert_eq!(parser.parse("1y")?, 31557600000000000);
+        assert_eq!(parser.parse("6mo")?, 15778800000000000);
+        assert_eq!(parser.parse("1m")?, 60000000000);
+        let parser = DurationParser::new().months_and_years(CalendarPolicy::Reject);
+        assert!(parser.parse("6mo").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_profiles() -> Result<(), Error> {
+        let go = DurationParser::profile(Profile::GoStrict);
+        assert_eq!(go.parse("1h30m")?, 5400000000000);
+        assert!(go.parse("1h 30m").is_err());
+        assert!(go.parse("1d").is_err());
+
+        let human = DurationParser::profile(Profile::HumanConfig);
+        assert_eq!(human.parse("1h 30m")?, 5400000000000);
+        assert_eq!(human.parse(" 1 h 30 mins ")?, 5400000000000);
+        assert_eq!(human.parse("2w")?, 1209600000000000);
+        assert_eq!(human.parse("-90 secs")?, -90000000000);
+        assert_eq!(human.parse(" 0 ")?, 0);
+        assert!(human.parse("1").is_err());
+        assert!(human.parse("1 y").is_err());
+
+        let ops = DurationParser::profile(Profile::OpsExtended);
+        assert_eq!(ops.parse("1y")?, 31536000000000000);
+        assert_eq!(ops.parse("7d")?, 604800000000000);
+        assert!(ops.parse("7 d").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unicode_minus() -> Result<(), Error> {
+        assert!(DurationParser::new().parse("\u{2212}1.5h").is_err());
+        let parser = DurationParser::new().unicode_minus();
+        assert_eq!(parser.parse("\u{2212}1.5h")?, -5400000000000);
+        assert_eq!(parser.parse("-1.5h")?, -5400000000000);
+        assert!(parser.parse("1h\u{2212}30m").is_err());
+        assert!(parser.non_negative().parse("\u{2212}0").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_negative() -> Result<(), Error> {
+        let parser = DurationParser::new().non_negative();
+        assert_eq!(parser.parse("1m")?, 60000000000);
+        assert_eq!(parser.parse("+1m")?, 60000000000);
+        assert_eq!(
+            parser.parse("-1m").unwrap_err(),
+            Error::NegativeDuration(String::from("-1m")),
+        );
+        assert!(parser.parse("-0s").is_err());
+        assert!(parser.parse("-0").is_err());
+
+        let names = DurationRegistry::new().with("rewind", -1000000000);
+        let parser = DurationParser::new().names(names).non_negative();
+        assert!(parser.parse("rewind").is_err());
+
+        // U+2212 is only a minus sign when the parser accepts it as one.
+        let names = DurationRegistry::new().with("\u{2212}0", 0);
+        let parser = DurationParser::new().names(names).non_negative();
+        assert_eq!(parser.parse("\u{2212}0")?, 0);
+        assert!(parser.unicode_minus().parse("\u{2212}0").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_scale() -> Result<(), Error> {
+        let parser = DurationParser::new().time_scale(TimeScale::new(0.01));
+        assert_eq!(parser.parse("1m")?, 600000000);
+        assert_eq!(parser.parse("-1h")?, -36000000000);
+        let parser = DurationParser::new().time_scale(TimeScale::new(1e9));
+        assert_eq!(parser.parse("1000h").unwrap_err(), Error::overflow("1000h"),);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantize_to() -> Result<(), Error> {
+        let parser = DurationParser::new().quantize_to(Unit::Millisecond, Rounding::Nearest);
+        assert_eq!(parser.parse("1.2345s")?, 1235000000);
+        assert_eq!(parser.parse("-1.2345s")?, -1235000000);
+        assert_eq!(parser.parse("400us")?, 0);
+        let parser = DurationParser::new().quantize_to(Unit::Second, Rounding::Ceil);
+        assert_eq!(parser.parse("1ms")?, 1000000000);
+        let parser = DurationParser::new().quantize_to(5000000000000000000i64, Rounding::Ceil);
+        assert_eq!(parser.parse("1h")?, 5000000000000000000);
+        let parser = parser.time_scale(TimeScale::new(2.0));
+        assert_eq!(
+            parser.parse("1000000h").unwrap_err(),
+            Error::overflow("1000000h"),
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "quantization step must be positive")]
+    fn test_quantize_to_zero() {
+        let _ = DurationParser::new().quantize_to(0i64, Rounding::Floor);
+    }
+
+    #[test]
+    fn test_names() -> Result<(), Error> {
+        let names = DurationRegistry::new()
+            .with("short", 5000000000)
+            .with("default-timeout", 30000000000);
+        let parser = DurationParser::new().names(names);
+        assert_eq!(parser.parse("short")?, 5000000000);
+        assert_eq!(parser.parse("default-timeout")?, 30000000000);
+        assert_eq!(parser.parse("5s")?, 5000000000);
+        assert!(parser.parse("long").is_err());
+        Ok(())
+    }
+}
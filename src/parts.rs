@@ -0,0 +1,329 @@
+//! The structure of duration strings, for tools that need the numbers and
+//! units a duration was written with rather than only its total.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::{
+    component_magnitude, leading_fraction, parse_duration, Error, InternalError, Span, Unit,
+    GO_UNITS, MAX_MAGNITUDE,
+};
+
+/// DurationParts is a parsed duration string: its sign and its components,
+/// in the order they're written.
+///
+/// ```rust
+/// use go_parse_duration::{DurationParts, Unit};
+///
+/// let parts = DurationParts::parse("-1h30.5m").unwrap();
+/// assert!(parts.negative);
+/// assert_eq!(parts.components[1].value, 30);
+/// assert_eq!(parts.components[1].fraction, "5");
+/// assert_eq!(parts.components[1].unit, Unit::Minute);
+/// assert_eq!(parts.nanos(), Some(-5430000000000));
+/// assert_eq!(parts.to_string(), "-1h30.5m");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationParts {
+    /// Whether the duration string starts with `-`.
+    pub negative: bool,
+    /// The components, which are empty for the duration "0".
+    pub components: Vec<Component>,
+}
+
+/// Component is one number and unit of a duration string, such as "1.5h".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// The integer part of the number.
+    pub value: u64,
+    /// The digits after the decimal point, without it. It's empty if the
+    /// number has no fraction.
+    pub fraction: String,
+    pub unit: Unit,
+    /// The location of the component in the duration string.
+    pub span: Span,
+}
+
+impl DurationParts {
+    /// parse parses a duration string into its parts. It accepts exactly
+    /// what [`parse_duration`] does, and fails with the same errors.
+    pub fn parse(string: &str) -> Result<DurationParts, Error> {
+        parse_duration(string)?;
+
+        let b = string.as_bytes();
+        let negative = b.first() == Some(&b'-');
+        let mut i = match b.first() {
+            Some(b'-') | Some(b'+') => 1,
+            _ => 0,
+        };
+        let mut components = Vec::new();
+        if &string[i..] == "0" {
+            return Ok(DurationParts {
+                negative,
+                components,
+            });
+        }
+        // The string is valid, so it's a run of [0-9]*(\.[0-9]*)?[^0-9.]+.
+        while i < b.len() {
+            let start = i;
+            while b[i].is_ascii_digit() {
+                i += 1;
+            }
+            let value = string[start..i].parse().unwrap_or(0);
+            let mut fraction = String::new();
+            if b[i] == b'.' {
+                i += 1;
+                let f = i;
+                while b[i].is_ascii_digit() {
+                    i += 1;
+                }
+                fraction.push_str(&string[f..i]);
+            }
+            let u = i;
+            while i < b.len() && b[i] != b'.' && !b[i].is_ascii_digit() {
+                i += 1;
+            }
+            components.push(Component {
+                value,
+                fraction,
+                unit: Unit::from_symbol(&string[u..i]).unwrap(),
+                span: Span::new(start, i - start),
+            });
+        }
+        Ok(DurationParts {
+            negative,
+            components,
+        })
+    }
+
+    /// nanos returns the total of the components in nanoseconds, with the
+    /// sign applied, or None if it doesn't fit in an i64.
+    pub fn nanos(&self) -> Option<i64> {
+        let mut d: u64 = 0;
+        for c in &self.components {
+            let (f, scale, _) = leading_fraction(&c.fraction);
+            d = d.checked_add(component_magnitude(
+                c.value,
+                f,
+                scale,
+                c.unit.nanos(),
+                false,
+            )?)?;
+        }
+        if self.negative {
+            if d > MAX_MAGNITUDE {
+                return None;
+            }
+            return Some((d as i64).wrapping_neg());
+        }
+        i64::try_from(d).ok()
+    }
+}
+
+impl fmt::Display for DurationParts {
+    // fmt writes the parts back as a Go duration string. It spells the
+    // microsecond unit "µs" whichever way it was written, drops a leading
+    // "+" and writes a missing integer part as "0", as in "0.5s" for ".5s".
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            formatter.write_str("-")?;
+        }
+        if self.components.is_empty() {
+            return formatter.write_str("0");
+        }
+        for c in &self.components {
+            write!(formatter, "{}", c.value)?;
+            if !c.fraction.is_empty() {
+                write!(formatter, ".{}", c.fraction)?;
+            }
+            formatter.write_str(c.unit.symbol())?;
+        }
+        Ok(())
+    }
+}
+
+/// DurationComponents iterates over the components of a duration string as
+/// they're parsed, yielding each number, with the sign of the duration, and
+/// its unit, for validating or evaluating durations without summing them.
+///
+/// The duration "0" has no components. Iteration stops after the first
+/// error, which is the one [`parse_duration`] would return, except that
+/// numbers are never too large for an f64, so there are no overflow errors.
+///
+/// ```rust
+/// use go_parse_duration::{DurationComponents, Unit};
+///
+/// let mut components = DurationComponents::new("-1h2.5m");
+/// assert_eq!(components.next(), Some(Ok((-1.0, Unit::Hour))));
+/// assert_eq!(components.next(), Some(Ok((-2.5, Unit::Minute))));
+/// assert_eq!(components.next(), None);
+///
+/// let mut components = DurationComponents::new("1h2d");
+/// assert_eq!(components.next(), Some(Ok((1.0, Unit::Hour))));
+/// assert!(components.next().unwrap().is_err());
+/// assert_eq!(components.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DurationComponents<'a> {
+    string: &'a str,
+    // The offset of the next component, or None once done.
+    pos: Option<usize>,
+    negative: bool,
+    first: bool,
+}
+
+impl<'a> DurationComponents<'a> {
+    /// new returns an iterator over the components of a duration string.
+    pub fn new(string: &'a str) -> DurationComponents<'a> {
+        let (pos, negative) = match string.as_bytes().first() {
+            Some(b'-') => (1, true),
+            Some(b'+') => (1, false),
+            _ => (0, false),
+        };
+        let pos = match &string[pos..] {
+            "0" => None,
+            _ => Some(pos),
+        };
+        DurationComponents {
+            string,
+            pos,
+            negative,
+            first: true,
+        }
+    }
+
+    // component parses the component at offset i, returning it and the
+    // offset of the next one.
+    fn component(&self, mut i: usize) -> Result<((f64, Unit), usize), InternalError> {
+        let b = self.string.as_bytes();
+        let start = i;
+        if i == b.len() || !(b[i] == b'.' || b[i].is_ascii_digit()) {
+            let len = self.string[i..].chars().next().map_or(0, char::len_utf8);
+            return Err(InternalError::Invalid(Span::new(i, len)));
+        }
+        while i < b.len() && b[i].is_ascii_digit() {
+            i += 1;
+        }
+        let pre = i != start;
+        if i < b.len() && b[i] == b'.' {
+            i += 1;
+        }
+        let f = i;
+        while i < b.len() && b[i].is_ascii_digit() {
+            i += 1;
+        }
+        if !pre && i == f {
+            // no digits (e.g. ".s" or "-.s")
+            return Err(InternalError::Invalid(Span::new(start, 1)));
+        }
+        let value: f64 = self.string[start..i].parse().unwrap_or(0.0);
+        let u = i;
+        while i < b.len() && b[i] != b'.' && !b[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == u {
+            return Err(InternalError::MissingUnit(Span::new(start, u - start)));
+        }
+        let unit = Unit::from_symbol(&self.string[u..i])
+            .ok_or(InternalError::UnknownUnit(Span::new(u, i - u)))?;
+        let value = if self.negative { -value } else { value };
+        Ok(((value, unit), i))
+    }
+}
+
+impl Iterator for DurationComponents<'_> {
+    type Item = Result<(f64, Unit), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let i = self.pos?;
+        if i == self.string.len() && !self.first {
+            self.pos = None;
+            return None;
+        }
+        match self.component(i) {
+            Ok((component, next)) => {
+                self.pos = Some(next);
+                self.first = false;
+                Some(Ok(component))
+            }
+            Err(e) => {
+                self.pos = None;
+                let e = e.into_error(self.string);
+                Some(Err(
+                    e.with_suggestions(GO_UNITS.iter().map(|(name, _)| *name))
+                ))
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for DurationComponents<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn test_duration_parts() -> Result<(), Error> {
+        let parts = DurationParts::parse("90m1.050s")?;
+        assert_eq!(
+            parts,
+            DurationParts {
+                negative: false,
+                components: vec![
+                    Component {
+                        value: 90,
+                        fraction: String::new(),
+                        unit: Unit::Minute,
+                        span: Span::new(0, 3),
+                    },
+                    Component {
+                        value: 1,
+                        fraction: String::from("050"),
+                        unit: Unit::Second,
+                        span: Span::new(3, 6),
+                    },
+                ],
+            }
+        );
+        assert_eq!(parts.to_string(), "90m1.050s");
+        assert_eq!(DurationParts::parse("+.5us")?.to_string(), "0.5µs");
+        assert_eq!(DurationParts::parse("-0")?.components, vec![]);
+        assert_eq!(
+            DurationParts::parse("1h30").unwrap_err(),
+            parse_duration("1h30").unwrap_err()
+        );
+        for v in crate::test_vectors::VECTORS {
+            match DurationParts::parse(v.input) {
+                Ok(parts) => assert_eq!(parts.nanos(), v.expected, "{}", v.input),
+                Err(_) => assert_eq!(v.expected, None, "{}", v.input),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration_components() {
+        let components = |s| DurationComponents::new(s).collect::<Result<Vec<_>, _>>();
+        assert_eq!(
+            components("1h30.5m"),
+            Ok(vec![(1.0, Unit::Hour), (30.5, Unit::Minute)])
+        );
+        assert_eq!(
+            components("-.5us1.ns"),
+            Ok(vec![(-0.5, Unit::Microsecond), (-1.0, Unit::Nanosecond)])
+        );
+        assert_eq!(components("0"), Ok(vec![]));
+        assert_eq!(components("-0"), Ok(vec![]));
+        for s in ["", "-", "1", "1h30", ".s", "1d", "1h-1h", "1mn"] {
+            assert_eq!(components(s), Err(parse_duration(s).unwrap_err()), "{}", s);
+        }
+        let mut components = DurationComponents::new("1s.");
+        assert_eq!(components.next(), Some(Ok((1.0, Unit::Second))));
+        assert!(components.next().unwrap().is_err());
+        assert_eq!(components.next(), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Kani proof harnesses for the overflow safety of the parser.
+//!
+//! They are compiled only by `cargo kani`, which sets `cfg(kani)`:
+//!
+//! ```sh
+//! cargo kani --harness parse_duration_never_panics
+//! ```
+//!
+//! Kani checks every harness for panics and arithmetic overflow on all the
+//! inputs it admits, so a harness with no assertion of its own still proves
+//! that the code it calls can't panic.
+use crate::{component_nanos, is_valid_duration, parse_duration, scan, Error, GO_UNITS};
+
+// MAX_LEN bounds the length of the symbolic duration strings. The parser's
+// own loops run at most once per byte, but matching a unit searches GO_UNITS,
+// and an unknown unit is compared with each of them for suggestions, so the
+// harnesses that parse are unwound GO_UNITS.len() + 1 times rather than
+// MAX_LEN + 2. #[kani::unwind] takes a literal, which UNWIND records.
+const MAX_LEN: usize = 6;
+const UNWIND: usize = 9;
+const _: () = assert!(UNWIND > GO_UNITS.len() && UNWIND > MAX_LEN + 1);
+
+// any_str returns a symbolic string of at most MAX_LEN bytes, or None if the
+// bytes chosen aren't valid UTF-8.
+fn any_str(bytes: &[u8; MAX_LEN]) -> Option<&str> {
+    let len: usize = kani::any();
+    kani::assume(len <= MAX_LEN);
+    std::str::from_utf8(&bytes[..len]).ok()
+}
+
+// parse_duration_never_panics proves that parse_duration returns on every
+// short string, whether valid or not.
+#[kani::proof]
+#[kani::unwind(9)]
+fn parse_duration_never_panics() {
+    let bytes: [u8; MAX_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let _ = parse_duration(s);
+    }
+}
+
+// is_valid_duration_agrees proves that is_valid_duration accepts exactly the
+// strings parse_duration does.
+#[kani::proof]
+#[kani::unwind(9)]
+fn is_valid_duration_agrees() {
+    let bytes: [u8; MAX_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        assert_eq!(is_valid_duration(s), parse_duration(s).is_ok());
+    }
+}
+
+// parse_duration_errors_are_syntax_errors proves that parse_duration only
+// fails with the errors of Go's grammar.
+#[kani::proof]
+#[kani::unwind(9)]
+fn parse_duration_errors_are_syntax_errors() {
+    let bytes: [u8; MAX_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        if let Err(e) = parse_duration(s) {
+            assert!(matches!(
+                e,
+                Error::Empty
+                    | Error::InvalidSyntax { .. }
+                    | Error::MissingUnit { .. }
+                    | Error::UnknownUnit { .. }
+                    | Error::Overflow { .. }
+            ));
+        }
+    }
+}
+
+// component_nanos_is_exact proves that a whole number of units is either
+// converted exactly or rejected, never wrapped, for every Go unit and any
+// value.
+#[kani::proof]
+fn component_nanos_is_exact() {
+    let v: i64 = kani::any();
+    kani::assume(v >= 0);
+    let i: usize = kani::any();
+    kani::assume(i < GO_UNITS.len());
+    let unit = GO_UNITS[i].1;
+    if let Some(ns) = component_nanos(v, 0, 1f64, unit) {
+        assert_eq!(i128::from(ns), i128::from(v) * i128::from(unit));
+    }
+}
+
+// component_nanos_never_wraps proves that a component with a fraction is
+// non-negative whenever it's accepted, for any positive unit length.
+#[kani::proof]
+fn component_nanos_never_wraps() {
+    let v: i64 = kani::any();
+    kani::assume(v >= 0);
+    let f: u64 = kani::any();
+    let digits: u32 = kani::any();
+    kani::assume(digits <= 19);
+    let scale = 10f64.powi(digits as i32);
+    kani::assume((f as f64) < scale);
+    let unit: i64 = kani::any();
+    kani::assume(unit > 0);
+    if let Some(ns) = component_nanos(v, f, scale, unit) {
+        assert!(ns >= 0);
+    }
+}
+
+// leading_int_is_exact proves that scan::leading_int returns the value of
+// the digits it consumes, or None rather than a wrapped value.
+#[kani::proof]
+#[kani::unwind(8)]
+fn leading_int_is_exact() {
+    let bytes: [u8; MAX_LEN] = kani::any();
+    if let Some(s) = any_str(&bytes) {
+        let mut expected = 0u128;
+        for c in s.bytes().take_while(u8::is_ascii_digit) {
+            expected = expected * 10 + u128::from(c - b'0');
+        }
+        if let Some((x, i)) = scan::leading_int(s) {
+            assert_eq!(u128::from(x), expected);
+            assert!(s.as_bytes()[..i].iter().all(u8::is_ascii_digit));
+        }
+    }
+}
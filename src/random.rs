@@ -0,0 +1,86 @@
+//! Random durations, available with the `rand` feature.
+use rand::distr::Distribution;
+use rand::{Rng, RngExt};
+
+use crate::{parse_duration, Error};
+
+/// DurationRange is an inclusive range of durations in nanoseconds that
+/// random durations can be sampled from, e.g. the jitter of a retry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DurationRange {
+    start: i64,
+    end: i64,
+}
+
+impl DurationRange {
+    /// new returns the range `start..=end`, or an error if start is after end.
+    pub fn new(start: i64, end: i64) -> Result<DurationRange, Error> {
+        if start > end {
+            return Err(Error::ParseError(format!(
+                "invalid duration range: {}..={}",
+                start, end
+            )));
+        }
+        Ok(DurationRange { start, end })
+    }
+
+    /// parse parses both ends of the range as Go duration strings.
+    pub fn parse(start: &str, end: &str) -> Result<DurationRange, Error> {
+        DurationRange::new(parse_duration(start)?, parse_duration(end)?)
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+}
+
+impl Distribution<i64> for DurationRange {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        rng.random_range(self.start..=self.end)
+    }
+}
+
+/// sample_between parses two duration strings and returns a duration in
+/// nanoseconds picked uniformly between them, both ends included.
+///
+/// ```rust
+/// use go_parse_duration::sample_between;
+/// use rand::rngs::SmallRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(42);
+/// let d = sample_between("100ms", "2s", &mut rng).unwrap();
+/// assert!(100000000 <= d && d <= 2000000000);
+/// ```
+pub fn sample_between<R: Rng + ?Sized>(start: &str, end: &str, rng: &mut R) -> Result<i64, Error> {
+    Ok(DurationRange::parse(start, end)?.sample(rng))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sample() -> Result<(), Error> {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let range = DurationRange::parse("100ms", "2s")?;
+        for _ in 0..100 {
+            let d = range.sample(&mut rng);
+            assert!((100000000..=2000000000).contains(&d));
+        }
+        assert_eq!(sample_between("1s", "1s", &mut rng)?, 1000000000);
+        assert_eq!(
+            sample_between("2s", "1s", &mut rng).unwrap_err(),
+            Error::ParseError(String::from(
+                "invalid duration range: 2000000000..=1000000000"
+            )),
+        );
+        Ok(())
+    }
+}
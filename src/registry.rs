@@ -0,0 +1,51 @@
+//! Named durations that configs can refer to instead of repeating literals.
+use std::collections::HashMap;
+
+/// DurationRegistry maps names such as "default-timeout" to durations in
+/// nanoseconds.
+///
+/// A [`DurationParser`](crate::DurationParser) given a registry resolves a
+/// duration string that is exactly one of its names to the named duration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DurationRegistry {
+    names: HashMap<String, i64>,
+}
+
+impl DurationRegistry {
+    /// new returns an empty registry.
+    pub fn new() -> DurationRegistry {
+        DurationRegistry::default()
+    }
+
+    /// with returns the registry with name mapped to the given duration,
+    /// replacing any existing mapping.
+    pub fn with(mut self, name: &str, nanos: i64) -> DurationRegistry {
+        self.insert(name, nanos);
+        self
+    }
+
+    /// insert maps name to the given duration, returning the duration it was
+    /// previously mapped to, if any.
+    pub fn insert(&mut self, name: &str, nanos: i64) -> Option<i64> {
+        self.names.insert(String::from(name), nanos)
+    }
+
+    /// get returns the duration name is mapped to.
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.names.get(name).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let mut registry = DurationRegistry::new().with("short", 5000000000);
+        assert_eq!(registry.get("short"), Some(5000000000));
+        assert_eq!(registry.insert("short", 1000000000), Some(5000000000));
+        assert_eq!(registry.get("short"), Some(1000000000));
+        assert_eq!(registry.get("long"), None);
+    }
+}
@@ -0,0 +1,76 @@
+//! Scaling parsed durations, e.g. to shrink timeouts in tests.
+
+/// TimeScale is a factor every parsed duration is multiplied by.
+///
+/// Integration tests can give their [`DurationParser`](crate::DurationParser)
+/// a scale of 0.01 to run with real-world timeouts shrunk a hundredfold,
+/// without editing the config files those timeouts come from.
+///
+/// ```rust
+/// use go_parse_duration::{DurationParser, TimeScale};
+///
+/// let parser = DurationParser::new().time_scale(TimeScale::new(0.01));
+/// assert_eq!(parser.parse("30s"), Ok(300000000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeScale(f64);
+
+impl TimeScale {
+    /// new returns a scale multiplying durations by factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if factor is negative, infinite or NaN.
+    pub fn new(factor: f64) -> TimeScale {
+        assert!(
+            factor.is_finite() && factor >= 0.0,
+            "time scale factor must be finite and non-negative"
+        );
+        TimeScale(factor)
+    }
+
+    /// factor returns the factor durations are multiplied by.
+    pub fn factor(self) -> f64 {
+        self.0
+    }
+
+    /// apply multiplies a duration in nanoseconds by the scale, rounding to
+    /// the nearest nanosecond, and returns None if the result overflows.
+    pub fn apply(self, ns: i64) -> Option<i64> {
+        if self.0 == 1.0 {
+            return Some(ns);
+        }
+        let scaled = (ns as f64 * self.0).round();
+        // i64::MAX isn't representable as f64, it rounds up to 2^63.
+        if scaled >= i64::MAX as f64 || scaled < i64::MIN as f64 {
+            return None;
+        }
+        Some(scaled as i64)
+    }
+}
+
+impl Default for TimeScale {
+    fn default() -> TimeScale {
+        TimeScale(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_scale() {
+        assert_eq!(TimeScale::new(0.01).apply(30000000000), Some(300000000));
+        assert_eq!(TimeScale::new(0.5).apply(-3), Some(-2));
+        assert_eq!(TimeScale::new(0.0).apply(i64::MAX), Some(0));
+        assert_eq!(TimeScale::new(2.0).apply(i64::MAX), None);
+        assert_eq!(TimeScale::default().apply(i64::MAX), Some(i64::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_factor() {
+        TimeScale::new(-1.0);
+    }
+}
@@ -0,0 +1,93 @@
+//! Scanning the numbers of duration-like grammars.
+//!
+//! These are the primitives [`parse_duration`](crate::parse_duration) is
+//! built on, for grammars of rates, sizes and the like that share its number
+//! syntax.
+
+/// leading_int consumes the leading `[0-9]*` from s and returns its value
+/// along with the number of bytes consumed, or None if the value overflows a
+/// u64.
+///
+/// ```rust
+/// use go_parse_duration::scan::leading_int;
+///
+/// assert_eq!(leading_int("125ms"), Some((125, 3)));
+/// assert_eq!(leading_int("ms"), Some((0, 0)));
+/// assert_eq!(leading_int("99999999999999999999"), None);
+/// ```
+pub fn leading_int(s: &str) -> Option<(u64, usize)> {
+    let mut x = 0u64;
+    let mut i = 0;
+    for &c in s.as_bytes() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        x = x.checked_mul(10)?.checked_add(u64::from(c - b'0'))?;
+        i += 1;
+    }
+    Some((x, i))
+}
+
+/// leading_fraction consumes the leading `[0-9]*` from s, the digits after a
+/// decimal point, and returns (value, scale, consumed) such that the
+/// fraction is value / scale.
+///
+/// It doesn't fail on overflow: digits that no longer fit in a u64 are
+/// consumed but don't add precision.
+///
+/// ```rust
+/// use go_parse_duration::scan::leading_fraction;
+///
+/// assert_eq!(leading_fraction("25s"), (25, 100f64, 2));
+/// ```
+pub fn leading_fraction(s: &str) -> (u64, f64, usize) {
+    let mut x = 0u64;
+    let mut scale = 1f64;
+    let mut overflow = false;
+    let mut i = 0;
+    for &c in s.as_bytes() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        i += 1;
+        if overflow {
+            continue;
+        }
+        match x
+            .checked_mul(10)
+            .and_then(|y| y.checked_add(u64::from(c - b'0')))
+        {
+            Some(y) => {
+                x = y;
+                scale *= 10f64;
+            }
+            None => overflow = true,
+        }
+    }
+    (x, scale, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_int() {
+        assert_eq!(leading_int(""), Some((0, 0)));
+        assert_eq!(leading_int("007h"), Some((7, 3)));
+        assert_eq!(leading_int("18446744073709551615"), Some((u64::MAX, 20)));
+        assert_eq!(leading_int("18446744073709551616"), None);
+        assert_eq!(leading_int("1µs"), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_leading_fraction() {
+        assert_eq!(leading_fraction("5"), (5, 10f64, 1));
+        assert_eq!(leading_fraction("050h"), (50, 1000f64, 3));
+        assert_eq!(leading_fraction("h"), (0, 1f64, 0));
+        let (x, scale, n) = leading_fraction("333333333333333333333333h");
+        assert_eq!(n, 24);
+        assert_eq!(x, 3333333333333333333);
+        assert_eq!(scale, 1e19);
+    }
+}
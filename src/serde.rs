@@ -0,0 +1,417 @@
+//! Serde support for durations, available with the `serde` feature.
+//!
+//! [`GoDuration`] serializes as a Go duration string and deserializes from
+//! one, so config structs can use it directly:
+//!
+//! ```rust
+//! use go_parse_duration::GoDuration;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     timeout: GoDuration,
+//! }
+//!
+//! let c: Config = serde_json::from_str(r#"{"timeout": "1m30s"}"#).unwrap();
+//! assert_eq!(c.timeout.to_string(), "1m30s");
+//! ```
+//!
+//! Fields that keep a plain type can use the modules here with
+//! `#[serde(with = "...")]` instead. [`nanos`], [`millis`] and [`secs`] read
+//! and write duration strings for `i64` fields in those units, and
+//! [`string`] and [`option`] do for `std::time::Duration` and
+//! `Option<std::time::Duration>` fields. [`list`] reads and writes
+//! comma-separated lists of durations:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use std::time::Duration;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "go_parse_duration::serde::millis")]
+//!     timeout_ms: i64,
+//!     #[serde(with = "go_parse_duration::serde::string")]
+//!     interval: Duration,
+//! }
+//!
+//! let c: Config =
+//!     serde_json::from_str(r#"{"timeout_ms": "1.5s", "interval": "1m"}"#).unwrap();
+//! assert_eq!(c.timeout_ms, 1500);
+//! assert_eq!(c.interval, Duration::from_secs(60));
+//! ```
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Error as _, Serialize, Serializer};
+
+use crate::{format_duration, parse_std, GoDuration};
+
+impl Serialize for GoDuration {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GoDuration {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<GoDuration, D::Error> {
+        struct V;
+
+        impl<'de2> Visitor<'de2> for V {
+            type Value = GoDuration;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Go duration string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<GoDuration, E> {
+                GoDuration::parse(v).map_err(E::custom)
+            }
+        }
+
+        d.deserialize_str(V)
+    }
+}
+
+// deserialize_in deserializes a duration string as a whole number of units
+// of the given length.
+fn deserialize_in<'de, D: Deserializer<'de>>(d: D, unit: i64, name: &str) -> Result<i64, D::Error> {
+    let v = GoDuration::deserialize(d)?.as_nanos();
+    if v % unit != 0 {
+        return Err(de::Error::custom(format!(
+            "duration {} is not a whole number of {}",
+            format_duration(v),
+            name
+        )));
+    }
+    Ok(v / unit)
+}
+
+// serialize_in serializes a number of units of the given length as a
+// duration string.
+fn serialize_in<S: Serializer>(v: i64, unit: i64, s: S) -> Result<S::Ok, S::Error> {
+    let v = v
+        .checked_mul(unit)
+        .ok_or_else(|| S::Error::custom("duration overflows i64 nanoseconds"))?;
+    GoDuration::from_nanos(v).serialize(s)
+}
+
+/// nanos (de)serializes an `i64` of nanoseconds as a duration string.
+pub mod nanos {
+    use serde::{Deserializer, Serializer};
+
+    use crate::consts::NANOSECOND;
+
+    /// deserialize deserializes nanoseconds from a duration string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<i64, D::Error> {
+        super::deserialize_in(d, NANOSECOND, "nanoseconds")
+    }
+
+    /// serialize serializes nanoseconds as a duration string.
+    pub fn serialize<S: Serializer>(v: &i64, s: S) -> Result<S::Ok, S::Error> {
+        super::serialize_in(*v, NANOSECOND, s)
+    }
+}
+
+/// millis (de)serializes an `i64` of milliseconds as a duration string.
+/// Durations that aren't a whole number of milliseconds fail to deserialize.
+pub mod millis {
+    use serde::{Deserializer, Serializer};
+
+    use crate::consts::MILLISECOND;
+
+    /// deserialize deserializes milliseconds from a duration string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<i64, D::Error> {
+        super::deserialize_in(d, MILLISECOND, "milliseconds")
+    }
+
+    /// serialize serializes milliseconds as a duration string.
+    pub fn serialize<S: Serializer>(v: &i64, s: S) -> Result<S::Ok, S::Error> {
+        super::serialize_in(*v, MILLISECOND, s)
+    }
+}
+
+/// secs (de)serializes an `i64` of seconds as a duration string. Durations
+/// that aren't a whole number of seconds fail to deserialize.
+pub mod secs {
+    use serde::{Deserializer, Serializer};
+
+    use crate::consts::SECOND;
+
+    /// deserialize deserializes seconds from a duration string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<i64, D::Error> {
+        super::deserialize_in(d, SECOND, "seconds")
+    }
+
+    /// serialize serializes seconds as a duration string.
+    pub fn serialize<S: Serializer>(v: &i64, s: S) -> Result<S::Ok, S::Error> {
+        super::serialize_in(*v, SECOND, s)
+    }
+}
+
+/// string (de)serializes a `std::time::Duration` as a duration string.
+/// Negative durations fail to deserialize.
+pub mod string {
+    use super::*;
+
+    /// deserialize deserializes a `Duration` from a duration string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        struct V;
+
+        impl<'de2> Visitor<'de2> for V {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Go duration string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+                parse_std(v).map_err(E::custom)
+            }
+        }
+
+        d.deserialize_str(V)
+    }
+
+    /// serialize serializes a `Duration` as a duration string.
+    pub fn serialize<S: Serializer>(v: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        let d = GoDuration::try_from(*v).map_err(S::Error::custom)?;
+        d.serialize(s)
+    }
+}
+
+/// option (de)serializes an `Option<std::time::Duration>` as a duration
+/// string or null. Add `#[serde(default)]` to the field so that it can be
+/// missing too.
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use std::time::Duration;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(default, with = "go_parse_duration::serde::option")]
+///     timeout: Option<Duration>,
+/// }
+///
+/// let c: Config = serde_json::from_str(r#"{"timeout": "5s"}"#).unwrap();
+/// assert_eq!(c.timeout, Some(Duration::from_secs(5)));
+/// let c: Config = serde_json::from_str("{}").unwrap();
+/// assert_eq!(c.timeout, None);
+/// ```
+pub mod option {
+    use super::*;
+
+    // Std wraps a Duration to (de)serialize it with the string module.
+    struct Std(Duration);
+
+    impl<'de> Deserialize<'de> for Std {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Std, D::Error> {
+            string::deserialize(d).map(Std)
+        }
+    }
+
+    impl Serialize for Std {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            string::serialize(&self.0, s)
+        }
+    }
+
+    /// deserialize deserializes an `Option<Duration>` from a duration string
+    /// or null.
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        let v: Option<Std> = Deserialize::deserialize(d)?;
+        Ok(v.map(|v| v.0))
+    }
+
+    /// serialize serializes an `Option<Duration>` as a duration string or
+    /// null.
+    pub fn serialize<S: Serializer>(v: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        match *v {
+            Some(d) => s.serialize_some(&Std(d)),
+            None => s.serialize_none(),
+        }
+    }
+}
+
+/// list (de)serializes a `Vec<i64>` of nanoseconds or a `Vec<GoDuration>`
+/// as a comma-separated list of duration strings, such as a retry schedule.
+/// It deserializes from an array of duration strings too.
+///
+/// ```rust
+/// use go_parse_duration::GoDuration;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(with = "go_parse_duration::serde::list")]
+///     backoff: Vec<GoDuration>,
+/// }
+///
+/// let c: Config = serde_json::from_str(r#"{"backoff": "1s,5s,30s"}"#).unwrap();
+/// assert_eq!(c.backoff.len(), 3);
+/// let c: Config = serde_json::from_str(r#"{"backoff": ["1s", "5s"]}"#).unwrap();
+/// assert_eq!(c.backoff.len(), 2);
+/// ```
+pub mod list {
+    use std::marker::PhantomData;
+
+    use serde::de::SeqAccess;
+
+    use super::*;
+
+    /// deserialize deserializes durations from a comma-separated list of
+    /// duration strings or an array of them.
+    pub fn deserialize<'de, T, D>(d: D) -> Result<Vec<T>, D::Error>
+    where
+        T: From<GoDuration>,
+        D: Deserializer<'de>,
+    {
+        struct V<T>(PhantomData<T>);
+
+        impl<'de2, T: From<GoDuration>> Visitor<'de2> for V<T> {
+            type Value = Vec<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a comma-separated list of Go duration strings")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<T>, E> {
+                if v.trim().is_empty() {
+                    return Ok(Vec::new());
+                }
+                v.split(',')
+                    .map(|s| GoDuration::parse(s.trim()).map(T::from).map_err(E::custom))
+                    .collect()
+            }
+
+            fn visit_seq<A: SeqAccess<'de2>>(self, mut seq: A) -> Result<Vec<T>, A::Error> {
+                // The hint comes from the input, so don't let it allocate more
+                // than a small buffer up front.
+                let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(d) = seq.next_element::<GoDuration>()? {
+                    v.push(T::from(d));
+                }
+                Ok(v)
+            }
+        }
+
+        d.deserialize_any(V(PhantomData))
+    }
+
+    /// serialize serializes durations as a comma-separated list of duration
+    /// strings.
+    pub fn serialize<T, S>(v: &[T], s: S) -> Result<S::Ok, S::Error>
+    where
+        T: Copy + Into<GoDuration>,
+        S: Serializer,
+    {
+        let list: Vec<String> = v.iter().map(|&d| d.into().to_string()).collect();
+        s.serialize_str(&list.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "nanos")]
+        nanos: i64,
+        #[serde(with = "millis")]
+        millis: i64,
+        #[serde(with = "secs")]
+        secs: i64,
+        #[serde(with = "string")]
+        string: Duration,
+    }
+
+    #[test]
+    fn test_go_duration() {
+        let d: GoDuration = serde_json::from_str(r#""-1.5s""#).unwrap();
+        assert_eq!(d, GoDuration::from_nanos(-1500000000));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""-1.5s""#);
+        let d = GoDuration::from_nanos(5400000000000);
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""1h30m0s""#);
+        assert!(serde_json::from_str::<GoDuration>(r#""1d""#).is_err());
+        assert!(serde_json::from_str::<GoDuration>("1000").is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Optional {
+        #[serde(default, with = "option")]
+        timeout: Option<Duration>,
+    }
+
+    #[test]
+    fn test_option() {
+        let c: Optional = serde_json::from_str(r#"{"timeout":"1.5s"}"#).unwrap();
+        assert_eq!(c.timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(serde_json::to_string(&c).unwrap(), r#"{"timeout":"1.5s"}"#);
+        let c: Optional = serde_json::from_str(r#"{"timeout":null}"#).unwrap();
+        assert_eq!(c.timeout, None);
+        assert_eq!(serde_json::to_string(&c).unwrap(), r#"{"timeout":null}"#);
+        let c: Optional = serde_json::from_str("{}").unwrap();
+        assert_eq!(c.timeout, None);
+        assert!(serde_json::from_str::<Optional>(r#"{"timeout":"1"}"#).is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Schedule {
+        #[serde(with = "list")]
+        nanos: Vec<i64>,
+        #[serde(with = "list")]
+        durations: Vec<GoDuration>,
+    }
+
+    #[test]
+    fn test_list() {
+        let json = r#"{"nanos":"1s, 5s,30s","durations":["1m","-2ms"]}"#;
+        let c: Schedule = serde_json::from_str(json).unwrap();
+        assert_eq!(c.nanos, [1000000000, 5000000000, 30000000000]);
+        assert_eq!(
+            c.durations,
+            [60000000000, -2000000].map(GoDuration::from_nanos)
+        );
+        assert_eq!(
+            serde_json::to_string(&c).unwrap(),
+            r#"{"nanos":"1s,5s,30s","durations":"1m0s,-2ms"}"#
+        );
+        let c: Schedule = serde_json::from_str(r#"{"nanos":"","durations":[]}"#).unwrap();
+        assert!(c.nanos.is_empty() && c.durations.is_empty());
+        let json = r#"{"nanos":"1s,,5s","durations":[]}"#;
+        assert!(serde_json::from_str::<Schedule>(json).is_err());
+    }
+
+    #[test]
+    fn test_with() {
+        let json = r#"{"nanos":"1.5µs","millis":"-2s","secs":"1h0m0s","string":"250ms"}"#;
+        let c: Config = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            c,
+            Config {
+                nanos: 1500,
+                millis: -2000,
+                secs: 3600,
+                string: Duration::from_millis(250),
+            }
+        );
+        assert_eq!(serde_json::to_string(&c).unwrap(), json);
+        let json = r#"{"nanos":"1s","millis":"1.5ms","secs":"1s","string":"1s"}"#;
+        let err = serde_json::from_str::<Config>(json).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("duration 1.5ms is not a whole number of milliseconds"));
+        let json = r#"{"nanos":"1s","millis":"1s","secs":"1s","string":"-1s"}"#;
+        assert!(serde_json::from_str::<Config>(json).is_err());
+        let c = Config {
+            secs: i64::MAX,
+            ..c
+        };
+        assert!(serde_json::to_string(&c).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! Measuring and printing elapsed time.
+use std::time::Instant;
+
+use crate::{align_to, format_duration, Rounding};
+
+/// format_elapsed returns the time elapsed since start as a Go duration
+/// string, rounded to the nearest multiple of precision, as Go's
+/// `time.Since(start).Round(precision).String()` does.
+///
+/// A precision that isn't positive leaves the duration unrounded.
+///
+/// ```rust
+/// use std::time::Instant;
+/// use go_parse_duration::stopwatch::format_elapsed;
+/// use go_parse_duration::Unit;
+///
+/// let start = Instant::now();
+/// println!("finished in {}", format_elapsed(start, Unit::Millisecond));
+/// ```
+pub fn format_elapsed<D: Into<i64>>(start: Instant, precision: D) -> String {
+    format_rounded(elapsed_nanos(start), precision.into())
+}
+
+/// Stopwatch measures the time elapsed since it was started.
+///
+/// ```rust
+/// use go_parse_duration::stopwatch::Stopwatch;
+/// use go_parse_duration::Unit;
+///
+/// let stopwatch = Stopwatch::start();
+/// println!("took {}", stopwatch.format(Unit::Microsecond));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch {
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// start returns a stopwatch started now.
+    pub fn start() -> Stopwatch {
+        Stopwatch {
+            start: Instant::now(),
+        }
+    }
+
+    /// elapsed returns the time elapsed since the stopwatch was started, in
+    /// nanoseconds.
+    pub fn elapsed(&self) -> i64 {
+        elapsed_nanos(self.start)
+    }
+
+    /// format returns the elapsed time as a Go duration string rounded to
+    /// precision, like [`format_elapsed`].
+    pub fn format<D: Into<i64>>(&self, precision: D) -> String {
+        format_elapsed(self.start, precision)
+    }
+
+    /// restart starts the stopwatch again and returns the time elapsed up to
+    /// now, in nanoseconds.
+    pub fn restart(&mut self) -> i64 {
+        let now = Instant::now();
+        let d = now.duration_since(self.start).as_nanos();
+        self.start = now;
+        nanos(d)
+    }
+}
+
+// elapsed_nanos returns the nanoseconds elapsed since start, saturating at
+// i64::MAX.
+fn elapsed_nanos(start: Instant) -> i64 {
+    nanos(start.elapsed().as_nanos())
+}
+
+fn nanos(d: u128) -> i64 {
+    if d > i64::MAX as u128 {
+        i64::MAX
+    } else {
+        d as i64
+    }
+}
+
+// format_rounded formats d rounded to the nearest multiple of precision.
+fn format_rounded(d: i64, precision: i64) -> String {
+    let d = if precision > 0 {
+        align_to(d, precision, Rounding::Nearest).unwrap_or(d)
+    } else {
+        d
+    };
+    format_duration(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Unit;
+
+    #[test]
+    fn test_format_rounded() {
+        assert_eq!(
+            format_rounded(1234567891, Unit::Millisecond.nanos()),
+            "1.235s"
+        );
+        assert_eq!(format_rounded(1234567891, Unit::Second.nanos()), "1s");
+        assert_eq!(format_rounded(1234567891, 0), "1.234567891s");
+        assert_eq!(format_rounded(400000, Unit::Millisecond.nanos()), "0s");
+        assert_eq!(
+            format_rounded(i64::MAX, Unit::Hour.nanos()),
+            "2562047h47m16.854775807s"
+        );
+    }
+
+    #[test]
+    fn test_stopwatch() {
+        let mut stopwatch = Stopwatch::start();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(stopwatch.elapsed() >= 10000000);
+        assert!(stopwatch.restart() >= 10000000);
+        assert!(stopwatch.elapsed() < 10000000000);
+        assert_eq!(stopwatch.format(Unit::Hour), "0s");
+    }
+}
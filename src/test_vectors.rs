@@ -0,0 +1,127 @@
+//! The conformance test vectors the crate is tested against, available with
+//! the `test-vectors` feature.
+//!
+//! Bindings and wrappers can run them against their own layer to check they
+//! parse exactly like [`parse_duration`](crate::parse_duration). Most of the
+//! vectors come from the tests of Go's `time` package.
+
+/// TestVector is a duration string and the outcome of parsing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    pub input: &'static str,
+    /// The parsed duration in nanoseconds, or None if parsing must fail.
+    pub expected: Option<i64>,
+}
+
+const fn ok(input: &'static str, ns: i64) -> TestVector {
+    TestVector {
+        input,
+        expected: Some(ns),
+    }
+}
+
+const fn err(input: &'static str) -> TestVector {
+    TestVector {
+        input,
+        expected: None,
+    }
+}
+
+/// VECTORS lists the conformance cases, valid ones first.
+pub const VECTORS: &[TestVector] = &[
+    // simple
+    ok("0", 0),
+    ok("5s", 5000000000),
+    ok("30s", 30000000000),
+    ok("1478s", 1478000000000),
+    // sign
+    ok("-5s", -5000000000),
+    ok("+5s", 5000000000),
+    ok("-0", 0),
+    ok("+0", 0),
+    // decimal
+    ok("5.0s", 5000000000),
+    ok("5.6s", 5600000000),
+    ok("5.s", 5000000000),
+    ok(".5s", 500000000),
+    ok("1.0s", 1000000000),
+    ok("1.00s", 1000000000),
+    ok("1.004s", 1004000000),
+    ok("1.0040s", 1004000000),
+    ok("100.00100s", 100001000000),
+    // different units
+    ok("10ns", 10),
+    ok("11us", 11000),
+    ok("12µs", 12000), // U+00B5
+    ok("12μs", 12000), // U+03BC
+    ok("13ms", 13000000),
+    ok("14s", 14000000000),
+    ok("15m", 900000000000),
+    ok("16h", 57600000000000),
+    // composite durations
+    ok("3h30m", 12600000000000),
+    ok("10.5s4m", 250500000000),
+    ok("-2m3.4s", -123400000000),
+    ok("1h2m3s4ms5us6ns", 3723004005006),
+    ok("39h9m14.425s", 140954425000000),
+    // large value
+    ok("52763797000ns", 52763797000),
+    // more than 9 digits after decimal point, see https://golang.org/issue/6617
+    ok("0.3333333333333333333h", 1200000000000),
+    // 9007199254740993 = 1<<53+1 cannot be stored precisely in a float64
+    ok("9007199254740993ns", 9007199254740993),
+    // huge string; issue 15011.
+    ok("0.100000000000000000000h", 360000000000),
+    // This value tests the first overflow check in leadingFraction.
+    ok("0.830103483285477580700h", 2988372539827),
+    // largest duration that can be represented by int64 in nanoseconds
+    ok("9223372036854775807ns", i64::MAX),
+    ok("9223372036854775.807us", i64::MAX),
+    ok("9223372036s854ms775us807ns", i64::MAX),
+    ok("-9223372036854775808ns", i64::MIN),
+    ok("-9223372036854775.808us", i64::MIN),
+    ok("-9223372036s854ms775us808ns", i64::MIN),
+    // largest negative value
+    ok("-9223372036854775808ns", i64::MIN),
+    // largest negative round trip value, see https://golang.org/issue/48629
+    ok("-2562047h47m16.854775808s", i64::MIN),
+    ok("2562047h47m16.854775807s", i64::MAX),
+    // errors
+    err(""),
+    err("3"),
+    err("-"),
+    err("s"),
+    err("."),
+    err("-."),
+    err(".s"),
+    err("+.s"),
+    err("1d"),
+    err("\u{FFFD}"),
+    err("\u{FFFD} hello \u{FFFD} world"),
+    // overflow
+    err("9223372036854775810ns"),
+    err("9223372036854775808ns"),
+    err("-9223372036854775809ns"),
+    err("9223372036854776us"),
+    err("3000000h"),
+    err("9223372036854775.808us"),
+    err("9223372036854ms775us808ns"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::try_parse_duration;
+
+    #[test]
+    fn test_vectors() {
+        for v in VECTORS {
+            assert_eq!(
+                try_parse_duration(v.input).ok(),
+                v.expected,
+                "{:?}",
+                v.input
+            );
+        }
+    }
+}
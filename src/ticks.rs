@@ -0,0 +1,84 @@
+//! Conversions to and from 100-nanosecond ticks, the unit of Windows
+//! `FILETIME` intervals and .NET `TimeSpan.Ticks`.
+
+/// TICK is the length of a tick in nanoseconds.
+pub const TICK: i64 = 100;
+
+/// to_ticks converts a duration in nanoseconds to ticks, truncating toward
+/// zero. It can't overflow.
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use go_parse_duration::{parse_duration, ticks::to_ticks};
+///
+/// assert_eq!(to_ticks(parse_duration("1.5s").unwrap()), 15000000);
+/// # }
+/// ```
+pub const fn to_ticks(ns: i64) -> i64 {
+    ns / TICK
+}
+
+/// from_ticks converts ticks to a duration in nanoseconds, returning None if
+/// it overflows.
+///
+/// ```rust
+/// use go_parse_duration::ticks::from_ticks;
+///
+/// assert_eq!(from_ticks(15000000), Some(1500000000));
+/// assert_eq!(from_ticks(i64::MAX), None);
+/// ```
+pub const fn from_ticks(ticks: i64) -> Option<i64> {
+    ticks.checked_mul(TICK)
+}
+
+/// saturating_from_ticks converts ticks to a duration in nanoseconds,
+/// clamping it to the range of i64 instead of overflowing.
+pub const fn saturating_from_ticks(ticks: i64) -> i64 {
+    ticks.saturating_mul(TICK)
+}
+
+/// to_filetime converts a duration in nanoseconds to an unsigned `FILETIME`
+/// interval, the u64 made of its `dwHighDateTime` and `dwLowDateTime`
+/// halves. It returns None for negative durations.
+pub const fn to_filetime(ns: i64) -> Option<u64> {
+    if ns < 0 {
+        None
+    } else {
+        Some(to_ticks(ns) as u64)
+    }
+}
+
+/// from_filetime converts an unsigned `FILETIME` interval to a duration in
+/// nanoseconds, returning None if it overflows.
+pub const fn from_filetime(filetime: u64) -> Option<i64> {
+    if filetime > i64::MAX as u64 {
+        None
+    } else {
+        from_ticks(filetime as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticks() {
+        assert_eq!(to_ticks(1000000000), 10000000);
+        assert_eq!(to_ticks(199), 1);
+        assert_eq!(to_ticks(-199), -1);
+        assert_eq!(to_ticks(i64::MIN), i64::MIN / 100);
+        assert_eq!(from_ticks(-10000000), Some(-1000000000));
+        assert_eq!(from_ticks(i64::MAX / 100 + 1), None);
+        assert_eq!(saturating_from_ticks(i64::MAX), i64::MAX);
+        assert_eq!(saturating_from_ticks(i64::MIN), i64::MIN);
+    }
+
+    #[test]
+    fn test_filetime() {
+        assert_eq!(to_filetime(3600000000000), Some(36000000000));
+        assert_eq!(to_filetime(-1), None);
+        assert_eq!(from_filetime(36000000000), Some(3600000000000));
+        assert_eq!(from_filetime(u64::MAX), None);
+    }
+}
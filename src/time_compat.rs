@@ -0,0 +1,56 @@
+// Conversions between durations and the time crate's, with the time feature.
+use std::convert::TryFrom;
+
+use crate::{parse_duration, Error, GoDuration, OverflowError};
+
+/// parse_time parses a duration string and returns it as a
+/// `time::Duration`.
+///
+/// ```rust
+/// use go_parse_duration::parse_time;
+///
+/// assert_eq!(parse_time("1m"), Ok(time::Duration::minutes(1)));
+/// ```
+pub fn parse_time(s: &str) -> Result<time::Duration, Error> {
+    parse_duration(s).map(time::Duration::nanoseconds)
+}
+
+impl From<GoDuration> for time::Duration {
+    fn from(d: GoDuration) -> time::Duration {
+        time::Duration::nanoseconds(d.as_nanos())
+    }
+}
+
+/// time durations longer than about 292 years don't fit in nanoseconds.
+impl TryFrom<time::Duration> for GoDuration {
+    type Error = OverflowError;
+
+    fn try_from(d: time::Duration) -> Result<GoDuration, OverflowError> {
+        i64::try_from(d.whole_nanoseconds())
+            .map(GoDuration::from_nanos)
+            .map_err(|_| OverflowError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() -> Result<(), Error> {
+        assert_eq!(parse_time("1h30m")?, time::Duration::minutes(90));
+        assert_eq!(parse_time("-1.5us")?, time::Duration::nanoseconds(-1500));
+        assert!(parse_time("1d").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversions() {
+        let d = GoDuration::from_nanos(i64::MIN);
+        assert_eq!(GoDuration::try_from(time::Duration::from(d)), Ok(d));
+        assert_eq!(
+            GoDuration::try_from(time::Duration::days(365 * 300)),
+            Err(OverflowError)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Sleeping and timeouts configured by duration strings.
+//!
+//! The async helpers need the `tokio` feature.
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::thread;
+
+#[cfg(feature = "tokio")]
+use tokio::time::{error::Elapsed, Interval};
+
+use crate::{parse_std, Error};
+
+/// std_sleep_str parses a duration string and blocks the current thread
+/// until it has elapsed.
+///
+/// Negative durations are rejected rather than treated as zero.
+pub fn std_sleep_str(s: &str) -> Result<(), Error> {
+    thread::sleep(parse_std(s)?);
+    Ok(())
+}
+
+/// sleep_str parses a duration string and waits until it has elapsed.
+///
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), go_parse_duration::Error> {
+/// go_parse_duration::timer::sleep_str("1ms").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn sleep_str(s: &str) -> Result<(), Error> {
+    tokio::time::sleep(parse_std(s)?).await;
+    Ok(())
+}
+
+/// timeout_str parses a duration string and requires fut to complete before
+/// it has elapsed.
+///
+/// The outer result holds the parse error, if any, and the inner one whether
+/// fut timed out.
+#[cfg(feature = "tokio")]
+pub async fn timeout_str<F: Future>(s: &str, fut: F) -> Result<Result<F::Output, Elapsed>, Error> {
+    Ok(tokio::time::timeout(parse_std(s)?, fut).await)
+}
+
+/// interval_str parses a duration string and returns a `tokio::time::Interval`
+/// ticking with that period, the first tick completing immediately.
+///
+/// Zero periods, which tokio doesn't support, are rejected along with
+/// negative ones.
+#[cfg(feature = "tokio")]
+pub fn interval_str(s: &str) -> Result<Interval, Error> {
+    let period = parse_std(s)?;
+    if period.is_zero() {
+        return Err(Error::ParseError(format!("zero interval period: {}", s)));
+    }
+    Ok(tokio::time::interval(period))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_std_sleep_str() -> Result<(), Error> {
+        let start = std::time::Instant::now();
+        std_sleep_str("10ms")?;
+        assert!(start.elapsed() >= Duration::from_millis(10));
+        assert_eq!(
+            std_sleep_str("-10ms").unwrap_err(),
+            Error::NegativeDuration(String::from("-10ms")),
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn test_sleep_str() -> Result<(), Error> {
+        let start = tokio::time::Instant::now();
+        sleep_str("1.5s").await?;
+        assert_eq!(start.elapsed(), Duration::from_millis(1500));
+        assert!(sleep_str("-1s").await.is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn test_timeout_str() -> Result<(), Error> {
+        assert_eq!(timeout_str("1s", async { 42 }).await?, Ok(42));
+        let slow = tokio::time::sleep(Duration::from_secs(60));
+        assert!(timeout_str("30s", slow).await?.is_err());
+        assert!(timeout_str("30", async {}).await.is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn test_interval_str() -> Result<(), Error> {
+        let start = tokio::time::Instant::now();
+        let mut interval = interval_str("5s")?;
+        interval.tick().await;
+        interval.tick().await;
+        interval.tick().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        assert_eq!(
+            interval_str("0s").unwrap_err(),
+            Error::ParseError(String::from("zero interval period: 0s")),
+        );
+        assert!(interval_str("-5s").is_err());
+        Ok(())
+    }
+}
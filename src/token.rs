@@ -0,0 +1,218 @@
+//! Splitting duration strings into classified tokens, e.g. for syntax
+//! highlighting.
+use std::ops::Range;
+
+use crate::unit_nanos;
+
+/// TokenKind classifies a part of a duration string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A leading `+` or `-`.
+    Sign,
+    /// The integer part of a number.
+    Number,
+    /// The decimal point and fractional digits of a number.
+    Fraction,
+    /// A known unit such as `ms`.
+    Unit,
+    /// Anything [`parse_duration`](crate::parse_duration) would reject, such
+    /// as an unknown unit.
+    Invalid,
+}
+
+/// Token is a classified part of a duration string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// The byte range of the token within the duration string.
+    pub span: Range<usize>,
+}
+
+/// tokenize splits a duration string into tokens covering all of it.
+///
+/// Tokenizing never fails: the parts of s that don't follow the grammar,
+/// including a number without a unit or a unit without a number, are
+/// reported as [`TokenKind::Invalid`] tokens.
+///
+/// ```rust
+/// use go_parse_duration::{tokenize, Token, TokenKind};
+///
+/// assert_eq!(
+///     tokenize("1.5hx"),
+///     vec![
+///         Token { kind: TokenKind::Number, span: 0..1 },
+///         Token { kind: TokenKind::Fraction, span: 1..3 },
+///         Token { kind: TokenKind::Invalid, span: 3..5 },
+///     ],
+/// );
+/// ```
+pub fn tokenize(s: &str) -> Vec<Token> {
+    let b = s.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    if s.starts_with(['-', '+']) {
+        tokens.push(Token {
+            kind: TokenKind::Sign,
+            span: 0..1,
+        });
+        i = 1;
+    }
+    while i < b.len() {
+        let start = i;
+        let kind = if b[i].is_ascii_digit() {
+            i += digits(&b[i..]);
+            TokenKind::Number
+        } else if b[i] == b'.' {
+            let n = digits(&b[i + 1..]);
+            let after_number =
+                tokens.last().map(|t| (t.kind, t.span.end)) == Some((TokenKind::Number, start));
+            i += 1 + n;
+            if n > 0 || after_number {
+                TokenKind::Fraction
+            } else {
+                // no digits (e.g. ".s")
+                TokenKind::Invalid
+            }
+        } else {
+            while i < b.len() && b[i] != b'.' && !b[i].is_ascii_digit() {
+                i += 1;
+            }
+            match unit_nanos(&s[start..i]) {
+                Some(_) => TokenKind::Unit,
+                None => TokenKind::Invalid,
+            }
+        };
+        tokens.push(Token {
+            kind,
+            span: start..i,
+        });
+    }
+    flag_missing(s, &mut tokens);
+    tokens
+}
+
+// flag_missing marks the tokens that are well formed on their own but lack
+// their other half as invalid: a number without a unit, a unit without a
+// number and a sign without anything after it. "0", which Go accepts without
+// a unit, is left alone.
+fn flag_missing(s: &str, tokens: &mut [Token]) {
+    match s.strip_prefix(['-', '+']).unwrap_or(s) {
+        "" => return invalidate(tokens),
+        "0" => return,
+        _ => {}
+    }
+    // The index of the first token of the number waiting for its unit.
+    let mut number: Option<usize> = None;
+    for j in 0..tokens.len() {
+        let kind = tokens[j].kind;
+        let starts_number = match kind {
+            TokenKind::Number => true,
+            TokenKind::Fraction => j == 0 || tokens[j - 1].kind != TokenKind::Number,
+            _ => false,
+        };
+        if starts_number {
+            if let Some(k) = number {
+                invalidate(&mut tokens[k..j]);
+            }
+            number = Some(j);
+        }
+        if kind == TokenKind::Unit && number.take().is_none() {
+            tokens[j].kind = TokenKind::Invalid;
+        }
+        if kind == TokenKind::Invalid {
+            number = None;
+        }
+    }
+    if let Some(k) = number {
+        invalidate(&mut tokens[k..]);
+    }
+}
+
+// invalidate marks all the tokens as invalid.
+fn invalidate(tokens: &mut [Token]) {
+    for t in tokens {
+        t.kind = TokenKind::Invalid;
+    }
+}
+
+// digits returns the number of leading ASCII digits in b.
+fn digits(b: &[u8]) -> usize {
+    b.iter().take_while(|c| c.is_ascii_digit()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(s: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(s)
+            .into_iter()
+            .map(|t| (t.kind, &s[t.span]))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        use TokenKind::*;
+
+        assert_eq!(
+            kinds("-1h30.5m"),
+            vec![
+                (Sign, "-"),
+                (Number, "1"),
+                (Unit, "h"),
+                (Number, "30"),
+                (Fraction, ".5"),
+                (Unit, "m"),
+            ]
+        );
+        assert_eq!(
+            kinds("2.µs"),
+            vec![(Number, "2"), (Fraction, "."), (Unit, "µs")]
+        );
+        assert_eq!(
+            kinds("5d1s"),
+            vec![(Number, "5"), (Invalid, "d"), (Number, "1"), (Unit, "s")]
+        );
+        assert_eq!(kinds(".s"), vec![(Invalid, "."), (Invalid, "s")]);
+        assert_eq!(
+            kinds("1s-2s"),
+            vec![(Number, "1"), (Invalid, "s-"), (Number, "2"), (Unit, "s")]
+        );
+        assert_eq!(kinds(""), vec![]);
+        assert_eq!(
+            kinds("1h30"),
+            vec![(Number, "1"), (Unit, "h"), (Invalid, "30")]
+        );
+        assert_eq!(kinds("h"), vec![(Invalid, "h")]);
+        assert_eq!(kinds("1"), vec![(Invalid, "1")]);
+        assert_eq!(kinds("+"), vec![(Invalid, "+")]);
+        assert_eq!(
+            kinds("1.5.5s"),
+            vec![
+                (Invalid, "1"),
+                (Invalid, ".5"),
+                (Fraction, ".5"),
+                (Unit, "s")
+            ]
+        );
+        assert_eq!(kinds("-0"), vec![(Sign, "-"), (Number, "0")]);
+    }
+
+    #[test]
+    fn test_tokenize_agrees_with_is_valid_duration() {
+        for s in [
+            "1h30", "h", "1", "+", "-", "", "0", "-0", "1.5.5s", ".s", "1s-2s", "5d1s", "2.µs",
+            "-1h30.5m", "1.5h", "1h30m",
+        ] {
+            // The empty string is invalid, but has no tokens to flag.
+            let valid = !tokenize(s).iter().any(|t| t.kind == TokenKind::Invalid);
+            assert_eq!(
+                valid,
+                crate::is_valid_duration(s) || s.is_empty(),
+                "{:?}",
+                s
+            );
+        }
+    }
+}
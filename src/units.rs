@@ -0,0 +1,457 @@
+//! Duration units and tables of them.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::consts::{HOUR, MICROSECOND, MILLISECOND, MINUTE, NANOSECOND, SECOND};
+use crate::GO_UNITS;
+
+/// Unit is one of the units Go formats durations with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+}
+
+impl Unit {
+    /// nanos returns the length of the unit in nanoseconds.
+    pub const fn nanos(self) -> i64 {
+        match self {
+            Unit::Nanosecond => NANOSECOND,
+            Unit::Microsecond => MICROSECOND,
+            Unit::Millisecond => MILLISECOND,
+            Unit::Second => SECOND,
+            Unit::Minute => MINUTE,
+            Unit::Hour => HOUR,
+        }
+    }
+
+    /// symbol returns the unit as Go writes it, e.g. "ms" or "µs".
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Unit::Nanosecond => "ns",
+            Unit::Microsecond => "µs",
+            Unit::Millisecond => "ms",
+            Unit::Second => "s",
+            Unit::Minute => "m",
+            Unit::Hour => "h",
+        }
+    }
+
+    // from_symbol returns the unit of one of the symbols Go accepts.
+    pub(crate) fn from_symbol(s: &str) -> Option<Unit> {
+        match s {
+            "ns" => Some(Unit::Nanosecond),
+            "us" | "µs" | "μs" => Some(Unit::Microsecond),
+            "ms" => Some(Unit::Millisecond),
+            "s" => Some(Unit::Second),
+            "m" => Some(Unit::Minute),
+            "h" => Some(Unit::Hour),
+            _ => None,
+        }
+    }
+}
+
+impl From<Unit> for i64 {
+    fn from(unit: Unit) -> i64 {
+        unit.nanos()
+    }
+}
+
+/// CalendarPolicy selects the length of months ("mo") and years ("y"), which
+/// vary in the calendar, see [`UnitTable::with_months_and_years`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarPolicy {
+    /// Months and years aren't accepted.
+    Reject,
+    /// A month is 30 days and a year 365 days.
+    Fixed,
+    /// A month is 30.4375 days and a year 365.25 days, their average lengths
+    /// in the Julian calendar.
+    Average,
+}
+
+/// UnitTable maps unit suffixes such as "ms" to their length in nanoseconds.
+///
+/// The default table holds Go's units; others can be added with
+/// [`UnitTable::with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitTable {
+    units: Vec<(String, i64)>,
+}
+
+impl UnitTable {
+    /// go returns a table of the units Go accepts: "ns", "us" (or "µs"),
+    /// "ms", "s", "m" and "h".
+    pub fn go() -> UnitTable {
+        UnitTable {
+            units: GO_UNITS
+                .iter()
+                .map(|&(name, ns)| (String::from(name), ns))
+                .collect(),
+        }
+    }
+
+    /// with returns the table with the given unit added, replacing any unit
+    /// with the same suffix.
+    pub fn with(mut self, suffix: &str, nanos: i64) -> UnitTable {
+        match self.units.iter_mut().find(|(name, _)| name == suffix) {
+            Some(unit) => unit.1 = nanos,
+            None => self.units.push((String::from(suffix), nanos)),
+        }
+        self
+    }
+
+    /// with_days_and_weeks returns the table with days ("d", 24 hours) and
+    /// weeks ("w", 7 days) added.
+    pub fn with_days_and_weeks(self) -> UnitTable {
+        self.with("d", 24 * HOUR).with("w", 7 * 24 * HOUR)
+    }
+
+    /// with_aliases returns the table with the short aliases "sec", "secs",
+    /// "min", "mins", "hr" and "hrs" added.
+    pub fn with_aliases(self) -> UnitTable {
+        self.with("sec", SECOND)
+            .with("secs", SECOND)
+            .with("min", MINUTE)
+            .with("mins", MINUTE)
+            .with("hr", HOUR)
+            .with("hrs", HOUR)
+    }
+
+    /// with_words returns the table with the English names of Go's units
+    /// added, singular and plural: "nanosecond", "microsecond",
+    /// "millisecond", "second", "minute" and "hour".
+    pub fn with_words(mut self) -> UnitTable {
+        let words = [
+            ("nanosecond", NANOSECOND),
+            ("microsecond", MICROSECOND),
+            ("millisecond", MILLISECOND),
+            ("second", SECOND),
+            ("minute", MINUTE),
+            ("hour", HOUR),
+        ];
+        for &(word, ns) in words.iter() {
+            self = self.with(word, ns).with(&format!("{}s", word), ns);
+        }
+        self
+    }
+
+    /// with_months_and_years returns the table with months ("mo") and years
+    /// ("y") added, with lengths chosen by the policy.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// use go_parse_duration::{CalendarPolicy, DurationParser, UnitTable};
+    ///
+    /// let table = UnitTable::go().with_months_and_years(CalendarPolicy::Fixed);
+    /// let parser = DurationParser::new().units(table);
+    /// assert_eq!(parser.parse("6mo"), Ok(15552000000000000));
+    /// # }
+    /// ```
+    pub fn with_months_and_years(self, policy: CalendarPolicy) -> UnitTable {
+        let day = 24 * HOUR;
+        match policy {
+            CalendarPolicy::Reject => self,
+            CalendarPolicy::Fixed => self.with("mo", 30 * day).with("y", 365 * day),
+            CalendarPolicy::Average => self
+                .with("mo", 30 * day + 10 * HOUR + 30 * MINUTE)
+                .with("y", 365 * day + 6 * HOUR),
+        }
+    }
+
+    /// with_si_seconds returns the table with kiloseconds ("ks") and
+    /// megaseconds ("Ms") added, as used in scientific contexts.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// use go_parse_duration::{DurationParser, UnitTable};
+    ///
+    /// let parser = DurationParser::new().units(UnitTable::go().with_si_seconds());
+    /// assert_eq!(parser.parse("1.2ks"), Ok(1200000000000));
+    /// assert_eq!(parser.parse("3Ms"), Ok(3000000000000000));
+    /// # }
+    /// ```
+    pub fn with_si_seconds(self) -> UnitTable {
+        self.with("ks", 1000 * SECOND).with("Ms", 1000000 * SECOND)
+    }
+
+    /// get returns the length of the unit in nanoseconds, if the table has it.
+    pub fn get(&self, suffix: &str) -> Option<i64> {
+        self.units
+            .iter()
+            .find(|(name, _)| name == suffix)
+            .map(|&(_, ns)| ns)
+    }
+
+    /// units returns an iterator over the suffixes in the table and their
+    /// lengths in nanoseconds.
+    pub fn units(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.units.iter().map(|(name, ns)| (name.as_str(), *ns))
+    }
+}
+
+impl Default for UnitTable {
+    fn default() -> UnitTable {
+        UnitTable::go()
+    }
+}
+
+/// UnitRegistry holds unit suffixes registered by the caller, such as "tick"
+/// for 100ns or "slot" for 12s, and resolves the unit of a duration to the
+/// longest registered suffix it starts with.
+///
+/// Unlike a [`UnitTable`], whose units end where a number or decimal point
+/// starts, registered suffixes may contain digits and dots, e.g. "x2". A
+/// match must still cover the whole run of letters Go would take as the
+/// unit, so "s" doesn't match the start of "slots".
+///
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use go_parse_duration::{DurationParser, UnitRegistry};
+///
+/// let mut units = UnitRegistry::new();
+/// units.register("tick", 100).register("slot", 12000000000);
+/// let parser = DurationParser::new().unit_registry(units);
+/// assert_eq!(parser.parse("2slot5tick"), Ok(24000000500));
+/// assert_eq!(parser.parse("1.5s"), Ok(1500000000));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitRegistry {
+    // Sorted by decreasing length, so the first match is the longest.
+    units: Vec<(String, i64)>,
+}
+
+impl UnitRegistry {
+    /// new returns a registry of the units Go accepts.
+    pub fn new() -> UnitRegistry {
+        UnitRegistry::from(UnitTable::go())
+    }
+
+    /// empty returns a registry without any units.
+    pub fn empty() -> UnitRegistry {
+        UnitRegistry { units: Vec::new() }
+    }
+
+    /// register adds a unit to the registry, replacing any unit with the
+    /// same suffix.
+    pub fn register(&mut self, suffix: &str, nanos: i64) -> &mut UnitRegistry {
+        match self.units.iter_mut().find(|(name, _)| name == suffix) {
+            Some(unit) => unit.1 = nanos,
+            None => {
+                let i = self
+                    .units
+                    .iter()
+                    .position(|(name, _)| name.len() < suffix.len())
+                    .unwrap_or(self.units.len());
+                self.units.insert(i, (String::from(suffix), nanos));
+            }
+        }
+        self
+    }
+
+    /// with returns the registry with the given unit added, like
+    /// [`UnitRegistry::register`].
+    pub fn with(mut self, suffix: &str, nanos: i64) -> UnitRegistry {
+        self.register(suffix, nanos);
+        self
+    }
+
+    /// resolve returns the longest registered suffix s starts with and its
+    /// length in nanoseconds.
+    pub fn resolve<'a>(&'a self, s: &str) -> Option<(&'a str, i64)> {
+        self.units
+            .iter()
+            .find(|(name, _)| s.starts_with(name.as_str()))
+            .map(|(name, ns)| (name.as_str(), *ns))
+    }
+
+    /// units returns the registered units and their lengths in
+    /// nanoseconds, longest suffix first.
+    pub fn units(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.units.iter().map(|(name, ns)| (name.as_str(), *ns))
+    }
+
+    #[cfg(feature = "std")]
+    // unit_at matches the unit at the start of s, at least run bytes long.
+    pub(crate) fn unit_at(&self, s: &str, run: usize) -> Option<(usize, i64)> {
+        self.units
+            .iter()
+            .find(|(name, _)| name.len() >= run && s.starts_with(name.as_str()))
+            .map(|(name, ns)| (name.len(), *ns))
+    }
+}
+
+impl Default for UnitRegistry {
+    fn default() -> UnitRegistry {
+        UnitRegistry::new()
+    }
+}
+
+impl From<UnitTable> for UnitRegistry {
+    fn from(table: UnitTable) -> UnitRegistry {
+        let mut registry = UnitRegistry::empty();
+        for (name, ns) in table.units() {
+            registry.register(name, ns);
+        }
+        registry
+    }
+}
+
+/// complete_unit returns the units in table starting with prefix, in sorted
+/// order, e.g. for shell completion or autocomplete of duration-valued
+/// fields.
+///
+/// prefix is the unit typed so far, without the number before it.
+///
+/// ```rust
+/// use go_parse_duration::{complete_unit, UnitTable};
+///
+/// assert_eq!(complete_unit("m", &UnitTable::go()), vec!["m", "ms"]);
+/// ```
+pub fn complete_unit<'a>(prefix: &str, table: &'a UnitTable) -> Vec<&'a str> {
+    let mut units: Vec<&str> = table
+        .units()
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with(prefix))
+        .collect();
+    units.sort_unstable();
+    units
+}
+
+/// suggest_unit returns the units in the table close to a unit the table
+/// doesn't have, closest first, for "did you mean" hints.
+///
+/// ```rust
+/// use go_parse_duration::{suggest_unit, UnitTable};
+///
+/// let table = UnitTable::go().with_aliases();
+/// assert_eq!(suggest_unit("mn", &table), ["m", "ms", "min"]);
+/// ```
+pub fn suggest_unit<'a>(unit: &str, table: &'a UnitTable) -> Vec<&'a str> {
+    suggest(unit, table.units().map(|(name, _)| name))
+}
+
+// suggest returns the names within a small edit distance of unit, closest
+// first. A suggestion must keep some of the unit, so "x" suggests nothing.
+pub(crate) fn suggest<'a, I>(unit: &str, names: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let len = unit.chars().count();
+    let max = if len <= 4 { 1 } else { 2 };
+    let mut close: Vec<(usize, &str)> = names
+        .into_iter()
+        .map(|name| (edit_distance(unit, name), name))
+        .filter(|&(d, _)| d <= max && d < len)
+        .collect();
+    // Shorter names first for each distance, then in table order.
+    close.sort_by_key(|&(d, name)| (d, name.len()));
+    close.dedup_by_key(|&mut (_, name)| name);
+    close.into_iter().take(3).map(|(_, name)| name).collect()
+}
+
+// edit_distance returns the Levenshtein distance between a and b, in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_unit_table() {
+        let table = UnitTable::go().with("d", 86400000000000).with("s", 1);
+        assert_eq!(table.get("d"), Some(86400000000000));
+        assert_eq!(table.get("s"), Some(1));
+        assert_eq!(table.get("ms"), Some(1000000));
+        assert_eq!(table.get("w"), None);
+        assert_eq!(UnitTable::default(), UnitTable::go());
+        let days = UnitTable::go().with_days_and_weeks();
+        assert_eq!(days.get("d"), Some(86400000000000));
+        assert_eq!(days.get("w"), Some(604800000000000));
+        let aliases = UnitTable::go().with_aliases();
+        assert_eq!(aliases.get("mins"), Some(60000000000));
+        assert_eq!(aliases.get("hr"), Some(3600000000000));
+        let words = UnitTable::go().with_words();
+        assert_eq!(words.get("hour"), Some(3600000000000));
+        assert_eq!(words.get("seconds"), Some(1000000000));
+        assert_eq!(words.units().count(), 20);
+        let fixed = UnitTable::go().with_months_and_years(CalendarPolicy::Fixed);
+        assert_eq!(fixed.get("mo"), Some(2592000000000000));
+        assert_eq!(fixed.get("y"), Some(31536000000000000));
+        let average = UnitTable::go().with_months_and_years(CalendarPolicy::Average);
+        assert_eq!(average.get("mo").map(|mo| mo * 12), average.get("y"));
+        assert_eq!(
+            UnitTable::go().with_months_and_years(CalendarPolicy::Reject),
+            UnitTable::go()
+        );
+        let si = UnitTable::go().with_si_seconds();
+        assert_eq!(si.get("ks"), Some(1000000000000));
+        assert_eq!(si.get("Ms"), Some(1000000000000000));
+        assert_eq!(si.get("ms"), Some(1000000));
+    }
+
+    #[test]
+    fn test_unit_registry() {
+        let mut units = UnitRegistry::empty();
+        units
+            .register("s", 1000000000)
+            .register("slot", 12000000000);
+        assert_eq!(units.resolve("slots"), Some(("slot", 12000000000)));
+        assert_eq!(units.resolve("sec"), Some(("s", 1000000000)));
+        assert_eq!(units.resolve("x"), None);
+        units.register("s", 1);
+        assert_eq!(units.resolve("s"), Some(("s", 1)));
+        assert_eq!(UnitRegistry::new().resolve("ms"), Some(("ms", 1000000)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_unit_at() {
+        let mut units = UnitRegistry::empty();
+        units
+            .register("s", 1000000000)
+            .register("slot", 12000000000);
+        assert_eq!(units.unit_at("slot5s", 4), Some((4, 12000000000)));
+        assert_eq!(units.unit_at("sec", 3), None);
+    }
+
+    #[test]
+    fn test_suggest_unit() {
+        let table = UnitTable::go().with_aliases();
+        assert_eq!(suggest_unit("hors", &table), ["hrs"]);
+        assert_eq!(suggest_unit("sek", &table), ["sec"]);
+        assert!(suggest_unit("d", &table).is_empty());
+        assert!(suggest_unit("fortnight", &table).is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("µs", "us"), 1);
+    }
+
+    #[test]
+    fn test_complete_unit() {
+        let table = UnitTable::go().with("min", 60000000000);
+        assert_eq!(complete_unit("m", &table), vec!["m", "min", "ms"]);
+        assert_eq!(complete_unit("n", &table), vec!["ns"]);
+        assert_eq!(complete_unit("x", &table), Vec::<&str>::new());
+        assert_eq!(complete_unit("", &UnitTable::go()).len(), 8);
+    }
+}
@@ -0,0 +1,70 @@
+//! Parsing duration strings encoded as UTF-16, as Win32 and OLE APIs return
+//! them.
+use std::char;
+use std::str;
+
+use crate::{parse_duration, Error};
+
+// BUF_LEN is the longest duration, in UTF-8 bytes, parsed without
+// allocating. Longer ones are valid but rare, e.g. with many leading zeros.
+const BUF_LEN: usize = 64;
+
+/// parse_duration_utf16 parses a UTF-16 duration string and returns duration
+/// in nanoseconds, like [`parse_duration`].
+///
+/// Durations of up to 64 bytes are transcoded on the stack, so the common
+/// case doesn't allocate unless the string is invalid.
+///
+/// ```rust
+/// use go_parse_duration::utf16::parse_duration_utf16;
+///
+/// let s: Vec<u16> = "1h30m".encode_utf16().collect();
+/// assert_eq!(parse_duration_utf16(&s), Ok(5400000000000));
+/// ```
+pub fn parse_duration_utf16(s: &[u16]) -> Result<i64, Error> {
+    let mut buf = [0u8; BUF_LEN];
+    let mut len = 0;
+    for c in char::decode_utf16(s.iter().copied()) {
+        let c = c.map_err(|_| invalid(s))?;
+        if len + c.len_utf8() > BUF_LEN {
+            return match String::from_utf16(s) {
+                Ok(s) => parse_duration(&s),
+                Err(_) => Err(invalid(s)),
+            };
+        }
+        len += c.encode_utf8(&mut buf[len..]).len();
+    }
+    // The buffer holds whole chars encoded as UTF-8.
+    parse_duration(str::from_utf8(&buf[..len]).unwrap())
+}
+
+// invalid returns the error for a string that isn't valid UTF-16.
+fn invalid(s: &[u16]) -> Error {
+    Error::invalid_syntax(&String::from_utf16_lossy(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn test_parse_duration_utf16() {
+        assert_eq!(parse_duration_utf16(&utf16("1h30m")), Ok(5400000000000));
+        assert_eq!(parse_duration_utf16(&utf16("300µs")), Ok(300000));
+        assert_eq!(parse_duration_utf16(&utf16("-1.5s")), Ok(-1500000000));
+        let long = format!("{}1s", "0".repeat(100));
+        assert_eq!(parse_duration_utf16(&utf16(&long)), Ok(1000000000));
+        assert_eq!(
+            parse_duration_utf16(&utf16("1d")),
+            Err(Error::unknown_unit("d", "1d")),
+        );
+        assert_eq!(
+            parse_duration_utf16(&[u16::from(b'1'), 0xD800, u16::from(b's')]),
+            Err(Error::invalid_syntax("1\u{FFFD}s")),
+        );
+    }
+}
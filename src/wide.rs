@@ -0,0 +1,185 @@
+//! Durations in i128 nanoseconds, for spans longer than the 292 years an
+//! i64 holds, e.g. in astronomy or simulations.
+//!
+//! ```rust
+//! use go_parse_duration::wide::{format_duration_i128, parse_duration_i128};
+//!
+//! // 1000 years of 365 days.
+//! let d = parse_duration_i128("8760000h").unwrap();
+//! assert_eq!(d, 31536000000000000000);
+//! assert_eq!(format_duration_i128(d), "8760000h0m0s");
+//! ```
+use alloc::format;
+use alloc::string::String;
+use core::convert::TryFrom;
+
+use crate::{format_duration, scan, unit_nanos, Error, InternalError, Span, GO_UNITS};
+
+/// parse_duration_i128 parses a duration string like
+/// [`parse_duration`](crate::parse_duration) and returns the duration in
+/// i128 nanoseconds, which only overflows past about 5.4e21 years.
+pub fn parse_duration_i128(string: &str) -> Result<i128, Error> {
+    parse(string).map_err(|e| {
+        e.into_error(string)
+            .with_suggestions(GO_UNITS.iter().map(|(name, _)| *name))
+    })
+}
+
+/// format_duration_i128 formats a duration in i128 nanoseconds like
+/// [`format_duration`], which it agrees with on every duration that fits in
+/// an i64.
+pub fn format_duration_i128(d: i128) -> String {
+    if let Ok(d) = i64::try_from(d) {
+        return format_duration(d);
+    }
+    // The duration is longer than an hour, so it's written in h, m and s.
+    let u = d.unsigned_abs();
+    let (secs, nanos) = (u / 1000000000, u % 1000000000);
+    let sign = if d < 0 { "-" } else { "" };
+    let frac = if nanos == 0 {
+        String::new()
+    } else {
+        let digits = format!(".{:09}", nanos);
+        String::from(digits.trim_end_matches('0'))
+    };
+    format!(
+        "{}{}h{}m{}{}s",
+        sign,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        frac
+    )
+}
+
+// parse parses a Go duration string into i128 nanoseconds.
+fn parse(string: &str) -> Result<i128, InternalError> {
+    let mut s = string;
+    let mut neg = false;
+    // Consume [-+]?
+    if let Some(&c) = s.as_bytes().first() {
+        if c == b'-' || c == b'+' {
+            neg = c == b'-';
+            s = &s[1..];
+        }
+    }
+    // Special case: if all that is left is "0", this is zero.
+    if s == "0" {
+        return Ok(0);
+    }
+    let pos = |s: &str| string.len() - s.len();
+    if s.is_empty() {
+        return Err(InternalError::Invalid(Span::new(pos(s), 0)));
+    }
+    let mut d: i128 = 0;
+    while !s.is_empty() {
+        let start = pos(s);
+
+        // The next character must be [0-9.]
+        let c = s.as_bytes()[0];
+        if !(c == b'.' || c.is_ascii_digit()) {
+            let len = s.chars().next().map_or(1, char::len_utf8);
+            return Err(InternalError::Invalid(Span::new(start, len)));
+        }
+        // Consume [0-9]*
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        let mut v: i128 = 0;
+        for c in s[..digits].bytes() {
+            v = v
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(i128::from(c - b'0')))
+                .ok_or(InternalError::Overflow(Span::new(start, digits)))?;
+        }
+        s = &s[digits..];
+
+        // Consume (\.[0-9]*)?
+        let (mut f, mut scale, mut post) = (0, 1f64, false);
+        if s.starts_with('.') {
+            let (f_, scale_, i) = scan::leading_fraction(&s[1..]);
+            f = f_;
+            scale = scale_;
+            post = i > 0;
+            s = &s[1 + i..];
+        }
+        if digits == 0 && !post {
+            // no digits (e.g. ".s" or "-.s")
+            return Err(InternalError::Invalid(Span::new(start, 1)));
+        }
+        let number = Span::new(start, pos(s) - start);
+
+        // Consume unit.
+        let i = s
+            .bytes()
+            .position(|c| c == b'.' || c.is_ascii_digit())
+            .unwrap_or(s.len());
+        if i == 0 {
+            return Err(InternalError::MissingUnit(number));
+        }
+        let unit = unit_nanos(&s[..i]).ok_or(InternalError::UnknownUnit(Span::new(pos(s), i)))?;
+        s = &s[i..];
+        let component = Span::new(start, pos(s) - start);
+
+        // The fraction is computed as parse_duration does, so they agree.
+        let frac = i128::from((f as f64 * (unit as f64 / scale)) as i64);
+        let c = v
+            .checked_mul(i128::from(unit))
+            .and_then(|c| c.checked_add(frac))
+            .ok_or(InternalError::Overflow(component))?;
+        d = d.checked_add(c).ok_or(InternalError::Overflow(component))?;
+    }
+    Ok(if neg { -d } else { d })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_duration;
+    use crate::test_vectors::VECTORS;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse_duration_i128() {
+        for v in VECTORS {
+            if let Ok(d) = parse_duration(v.input) {
+                assert_eq!(
+                    parse_duration_i128(v.input),
+                    Ok(i128::from(d)),
+                    "{}",
+                    v.input
+                );
+            }
+        }
+        assert_eq!(
+            parse_duration_i128("9223372036854775808ns"),
+            Ok(i128::from(i64::MAX) + 1)
+        );
+        assert_eq!(
+            parse_duration_i128("-3000000h1.5s"),
+            Ok(-10800000001500000000)
+        );
+        assert_eq!(
+            parse_duration_i128("1x").unwrap_err().to_string(),
+            "Parse error: unknown unit x in duration 1x"
+        );
+        let max = format!("{}ns", i128::MAX);
+        assert_eq!(parse_duration_i128(&max), Ok(i128::MAX));
+        assert!(parse_duration_i128(&format!("{}h", i128::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_format_duration_i128() {
+        assert_eq!(format_duration_i128(1500000), "1.5ms");
+        assert_eq!(
+            format_duration_i128(i128::from(i64::MIN)),
+            format_duration(i64::MIN)
+        );
+        assert_eq!(
+            format_duration_i128(-10800000001500000000),
+            "-3000000h0m1.5s"
+        );
+        let d = i128::MAX;
+        assert_eq!(parse_duration_i128(&format_duration_i128(d)), Ok(d));
+        let d = i128::MIN + 1;
+        assert_eq!(parse_duration_i128(&format_duration_i128(d)), Ok(d));
+    }
+}